@@ -0,0 +1,145 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::answers::normalize;
+
+// What a part produces. Grids are screens of lit and unlit pixels, such as day 10's CRT.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    Grid(Vec<Vec<bool>>),
+}
+
+const LIT: char = '#';
+const UNLIT: char = '.';
+
+impl Answer {
+    // Reads an answer written down by hand, e.g. in src/data/answers.txt. A block of '#' and '.'
+    // rows is a grid, a whole number an integer and anything else text.
+    pub fn parse(recorded: &str) -> Answer {
+        let recorded = normalize(recorded);
+        if recorded.contains('\n')
+            && recorded
+                .lines()
+                .all(|row| row.chars().all(|c| c == LIT || c == UNLIT))
+        {
+            return Answer::Grid(
+                recorded
+                    .lines()
+                    .map(|row| row.chars().map(|c| c == LIT).collect())
+                    .collect(),
+            );
+        }
+
+        match recorded.parse::<i128>() {
+            Ok(value) => Answer::Integer(value),
+            Err(_) => Answer::Text(recorded),
+        }
+    }
+
+    pub fn is_multi_line(&self) -> bool {
+        match self {
+            Answer::Integer(_) => false,
+            Answer::Text(text) => text.contains('\n'),
+            Answer::Grid(rows) => rows.len() > 1,
+        }
+    }
+
+    // The answer's lines, for output formats that lay multi-line answers out themselves.
+    pub fn lines(&self) -> Vec<String> {
+        self.to_string().lines().map(str::to_owned).collect()
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{}", value),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Grid(rows) => {
+                let rows: Vec<String> = rows
+                    .iter()
+                    .map(|row| {
+                        row.iter()
+                            .map(|lit| if *lit { LIT } else { UNLIT })
+                            .collect()
+                    })
+                    .collect();
+                write!(f, "{}", rows.join("\n"))
+            }
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Self {
+        Answer::Integer(value.into())
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Self {
+        Answer::Integer(value.into())
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Answer::Integer(value.into())
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Integer(value as i128)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_recorded_answers() {
+        assert_eq!(Answer::parse("17408399184"), Answer::Integer(17408399184));
+        assert_eq!(Answer::parse("-3"), Answer::Integer(-3));
+        assert_eq!(
+            Answer::parse("VGBBJCRMN"),
+            Answer::Text(String::from("VGBBJCRMN"))
+        );
+        assert_eq!(
+            Answer::parse("\n    #.\n    .#\n"),
+            Answer::Grid(vec![vec![true, false], vec![false, true]])
+        );
+        assert_eq!(Answer::parse("#."), Answer::Text(String::from("#.")));
+    }
+
+    #[test]
+    fn rendering() {
+        let grid = Answer::Grid(vec![vec![true, false], vec![false, true]]);
+        assert_eq!(grid.to_string(), "#.\n.#");
+        assert_eq!(grid.lines(), vec!["#.", ".#"]);
+        assert!(grid.is_multi_line());
+
+        assert_eq!(Answer::from(24000u32).to_string(), "24000");
+        assert_eq!(Answer::from(-7).to_string(), "-7");
+        assert!(!Answer::from(String::from("CMZ")).is_multi_line());
+    }
+
+    #[test]
+    fn rendered_answers_parse_back() {
+        for answer in [
+            Answer::from(1794u32),
+            Answer::from(String::from("MCD")),
+            Answer::Grid(vec![vec![true, true, false]; 6]),
+        ] {
+            assert_eq!(Answer::parse(&answer.to_string()), answer);
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::BTreeMap;
+
+use crate::solution::Part;
+
+pub const ANSWERS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/data/answers.txt");
+
+pub type Answers = BTreeMap<(u8, Part), String>;
+
+// Reduces an answer to its non-blank lines, ignoring any indentation a multi-line answer
+// such as day 10's screen was written down with.
+pub fn normalize(answer: &str) -> String {
+    answer
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
+
+fn parse_key(key: &str) -> Option<(u8, Part)> {
+    let (day, part) = key.split_once(' ')?;
+    let day = day.strip_prefix("day")?.parse::<u8>().ok()?;
+    let part = match part.trim() {
+        "part1" => Part::One,
+        "part2" => Part::Two,
+        _ => return None,
+    };
+    Some((day, part))
+}
+
+pub fn parse_answers(input: &str) -> Result<Answers, String> {
+    let mut answers = Answers::new();
+    let mut multi_line_answer: Option<((u8, Part), Vec<&str>)> = None;
+
+    for (index, line) in input.lines().enumerate() {
+        let line = line.trim();
+
+        if let Some((key, rows)) = multi_line_answer.as_mut() {
+            if !line.is_empty() {
+                rows.push(line);
+                continue;
+            }
+            answers.insert(*key, rows.join("\n"));
+            multi_line_answer = None;
+            continue;
+        }
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (key, answer) = line
+            .split_once(':')
+            .ok_or_else(|| format!("line {}: expected 'dayNN partN: answer'", index + 1))?;
+        let key =
+            parse_key(key).ok_or_else(|| format!("line {}: invalid key '{}'", index + 1, key))?;
+        if answers.contains_key(&key) {
+            return Err(format!(
+                "line {}: duplicate answer for '{}'",
+                index + 1,
+                line
+            ));
+        }
+
+        match answer.trim() {
+            "" => multi_line_answer = Some((key, Vec::new())),
+            answer => {
+                answers.insert(key, answer.to_owned());
+            }
+        }
+    }
+
+    if let Some((key, rows)) = multi_line_answer {
+        answers.insert(key, rows.join("\n"));
+    }
+
+    Ok(answers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_answers() {
+        let answers = parse_answers(
+            "# comment
+day01 part1: 71300
+day05 part2: LBBVJBRMH
+day10 part2:
+  ##..
+  #..#
+
+day11 part1: 62491",
+        )
+        .unwrap();
+
+        assert_eq!(answers.len(), 4);
+        assert_eq!(answers[&(1, Part::One)], "71300");
+        assert_eq!(answers[&(5, Part::Two)], "LBBVJBRMH");
+        assert_eq!(answers[&(10, Part::Two)], "##..\n#..#");
+        assert_eq!(answers[&(11, Part::One)], "62491");
+    }
+
+    #[test]
+    fn invalid_answers() {
+        assert!(parse_answers("day01 part3: 1").is_err());
+        assert!(parse_answers("day01: 1").is_err());
+        assert!(parse_answers("71300").is_err());
+        assert!(parse_answers("day01 part1: 1\nday01 part1: 2").is_err());
+    }
+
+    #[test]
+    fn normalizing_answers() {
+        assert_eq!(normalize("71300"), "71300");
+        assert_eq!(normalize("\n        ##..\n        #..#\n"), "##..\n#..#");
+    }
+
+    // Days whose input is still empty have nothing to record yet.
+    #[test]
+    fn recorded_answers_cover_every_day() {
+        let answers = parse_answers(&std::fs::read_to_string(ANSWERS_PATH).unwrap()).unwrap();
+        for solution in crate::solution::SOLUTIONS {
+            let (_, input) = crate::cli::read_default_input(*solution).unwrap();
+            if crate::input::is_blank(&input) {
+                continue;
+            }
+            assert!(answers.contains_key(&(solution.day(), Part::One)));
+            assert!(answers.contains_key(&(solution.day(), Part::Two)));
+        }
+    }
+}
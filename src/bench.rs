@@ -0,0 +1,313 @@
+use std::fmt::{self, Display, Formatter};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::cli::{read_input, Format, Options};
+use crate::error::{ParseError, SolveError};
+use crate::output::{csv_field, json_string, stage_failure_message, summarize_failures};
+use crate::runner::catch_panics;
+use crate::solution::{get_solution, solve_named, Part, Solution};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Stage {
+    Parse,
+    Solve(Part),
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Solve(part) => write!(f, "part {}", part),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &mut [Duration]) -> Option<Stats> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort();
+
+        let middle = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[middle - 1] + samples[middle]) / 2
+        } else {
+            samples[middle]
+        };
+
+        Some(Stats {
+            min: samples[0],
+            median,
+            mean: samples.iter().sum::<Duration>() / samples.len() as u32,
+            max: samples[samples.len() - 1],
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Measurement {
+    pub day: u8,
+    pub stage: Stage,
+    pub iterations: usize,
+    // A stage that panics or can't parse the input is recorded with its error, and the others
+    // are still timed.
+    pub stats: Result<Stats, SolveError>,
+}
+
+pub fn time<T>(
+    iterations: usize,
+    mut run: impl FnMut() -> Result<T, ParseError>,
+) -> Result<Stats, ParseError> {
+    let mut samples: Vec<Duration> = Vec::with_capacity(iterations);
+    for _ in 0..iterations.max(1) {
+        let start = Instant::now();
+        black_box(run()?);
+        samples.push(start.elapsed());
+    }
+    Ok(Stats::from_samples(&mut samples).unwrap())
+}
+
+// Parts are timed with the named implementation, which must exist for each of them.
+pub fn bench(
+    solution: &dyn Solution,
+    implementation: &str,
+    parts: &[Part],
+    input: &str,
+    iterations: usize,
+) -> Vec<Measurement> {
+    let mut stages = vec![Stage::Parse];
+    stages.extend(parts.iter().map(|part| Stage::Solve(*part)));
+
+    stages
+        .into_iter()
+        .map(|stage| {
+            let stats = match stage {
+                Stage::Parse => {
+                    catch_panics(|| time(iterations, || solution.parse(black_box(input))))
+                }
+                Stage::Solve(part) => catch_panics(|| {
+                    time(iterations, || {
+                        solve_named(solution, implementation, part, black_box(input))
+                    })
+                }),
+            };
+            Measurement {
+                day: solution.day(),
+                stage,
+                iterations: iterations.max(1),
+                stats,
+            }
+        })
+        .collect()
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos < 1_000 {
+        format!("{} ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.1} µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2} ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2} s", nanos as f64 / 1e9)
+    }
+}
+
+pub fn print_text(measurements: &[Measurement]) {
+    println!(
+        "Day  Stage   {:>10}  {:>10}  {:>10}  {:>10}",
+        "Min", "Median", "Mean", "Max"
+    );
+    for measurement in measurements {
+        match &measurement.stats {
+            Ok(stats) => println!(
+                "{:02}   {:<6}  {:>10}  {:>10}  {:>10}  {:>10}",
+                measurement.day,
+                measurement.stage.to_string(),
+                format_duration(stats.min),
+                format_duration(stats.median),
+                format_duration(stats.mean),
+                format_duration(stats.max),
+            ),
+            Err(error) => println!(
+                "{:02}   {:<6}  failed: {}",
+                measurement.day,
+                measurement.stage.to_string(),
+                error
+            ),
+        }
+    }
+}
+
+pub fn print_json(measurements: &[Measurement]) {
+    let records: Vec<String> = measurements
+        .iter()
+        .map(|measurement| {
+            let outcome = match &measurement.stats {
+                Ok(stats) => format!(
+                    "\"min_ns\":{},\"median_ns\":{},\"mean_ns\":{},\"max_ns\":{}",
+                    stats.min.as_nanos(),
+                    stats.median.as_nanos(),
+                    stats.mean.as_nanos(),
+                    stats.max.as_nanos(),
+                ),
+                Err(error) => format!("\"error\":{}", json_string(&error.to_string())),
+            };
+            format!(
+                "  {{\"day\":{},\"stage\":{},\"iterations\":{},{}}}",
+                measurement.day,
+                json_string(&measurement.stage.to_string()),
+                measurement.iterations,
+                outcome,
+            )
+        })
+        .collect();
+    println!("[\n{}\n]", records.join(",\n"));
+}
+
+pub fn print_csv(measurements: &[Measurement]) {
+    println!("day,stage,iterations,min_ns,median_ns,mean_ns,max_ns,error");
+    for measurement in measurements {
+        let (durations, error) = match &measurement.stats {
+            Ok(stats) => (
+                [stats.min, stats.median, stats.mean, stats.max]
+                    .map(|duration| duration.as_nanos().to_string()),
+                String::new(),
+            ),
+            Err(error) => (Default::default(), error.to_string()),
+        };
+        println!(
+            "{},{},{},{},{}",
+            measurement.day,
+            measurement.stage,
+            measurement.iterations,
+            durations.join(","),
+            csv_field(&error),
+        );
+    }
+}
+
+// Like a normal run, a day that fails doesn't stop the others being measured; the failures are
+// listed at the end.
+pub fn run(options: &Options) -> Result<(), String> {
+    let mut measurements: Vec<Measurement> = Vec::new();
+    let mut failures: Vec<String> = Vec::new();
+
+    for day in &options.days {
+        let solution = get_solution(*day).ok_or(format!("day {} is not registered", day))?;
+        let (input_name, input) = read_input(options, solution)?;
+
+        for measurement in bench(
+            solution,
+            options.implementation(),
+            &options.parts,
+            &input,
+            options.iterations,
+        ) {
+            if let Err(error) = &measurement.stats {
+                failures.push(stage_failure_message(
+                    measurement.day,
+                    &measurement.stage.to_string(),
+                    &input_name,
+                    error,
+                ));
+            }
+            measurements.push(measurement);
+        }
+    }
+
+    match options.format {
+        Format::Text => print_text(&measurements),
+        Format::Json => print_json(&measurements),
+        Format::Csv => print_csv(&measurements),
+    }
+
+    match summarize_failures(failures, measurements.len(), "stages") {
+        Some(summary) => Err(summary),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day01::Day01;
+    use crate::day08::Day08;
+    use crate::solution::DEFAULT_IMPLEMENTATION;
+
+    #[test]
+    fn stats_from_samples() {
+        let mut samples: Vec<Duration> = [5, 1, 4, 2].map(Duration::from_micros).to_vec();
+        assert_eq!(
+            Stats::from_samples(&mut samples),
+            Some(Stats {
+                min: Duration::from_micros(1),
+                median: Duration::from_micros(3),
+                mean: Duration::from_micros(3),
+                max: Duration::from_micros(5),
+            })
+        );
+
+        let mut samples: Vec<Duration> = [7, 1, 3].map(Duration::from_micros).to_vec();
+        assert_eq!(
+            Stats::from_samples(&mut samples).unwrap().median,
+            Duration::from_micros(3)
+        );
+        assert_eq!(Stats::from_samples(&mut []), None);
+    }
+
+    #[test]
+    fn bench_every_stage() {
+        let measurements = bench(
+            &Day01,
+            DEFAULT_IMPLEMENTATION,
+            &[Part::One, Part::Two],
+            "1000\n\n2000",
+            3,
+        );
+        let stages: Vec<Stage> = measurements.iter().map(|m| m.stage).collect();
+        assert_eq!(
+            stages,
+            vec![
+                Stage::Parse,
+                Stage::Solve(Part::One),
+                Stage::Solve(Part::Two)
+            ]
+        );
+        assert!(measurements
+            .iter()
+            .all(|m| m.iterations == 3 && m.day == 1 && m.stats.is_ok()));
+    }
+
+    #[test]
+    fn failing_stages_are_recorded() {
+        let measurements = bench(&Day01, DEFAULT_IMPLEMENTATION, &[Part::One], "10x0", 3);
+        assert_eq!(measurements.len(), 2);
+        assert!(measurements
+            .iter()
+            .all(|m| matches!(m.stats, Err(SolveError::Parse(_)))));
+
+        // An empty forest has no interior to walk.
+        let measurements = bench(&Day08, DEFAULT_IMPLEMENTATION, &[Part::One], "", 1);
+        assert!(measurements[1].stats.is_err());
+    }
+
+    #[test]
+    fn formatting_durations() {
+        assert_eq!(format_duration(Duration::from_nanos(999)), "999 ns");
+        assert_eq!(format_duration(Duration::from_nanos(12_345)), "12.3 µs");
+        assert_eq!(format_duration(Duration::from_micros(4_560)), "4.56 ms");
+        assert_eq!(format_duration(Duration::from_millis(2_500)), "2.50 s");
+    }
+}
@@ -0,0 +1,1040 @@
+use std::env;
+use std::ffi::OsString;
+use std::fmt::{Display, Formatter};
+use std::fs::{read_dir, read_to_string};
+use std::io::{self, Read};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::bench;
+use crate::compare;
+use crate::embedded;
+use crate::examples;
+use crate::input::normalize;
+use crate::memory;
+use crate::output;
+use crate::runner::{self, Job};
+use crate::solution::{
+    get_solution, Part, Solution, DEFAULT_IMPLEMENTATION, DEFAULT_INPUT_DIR, SOLUTIONS,
+};
+use crate::watch;
+
+pub const USAGE: &str = "Usage: adventofcode_2022_rust [OPTIONS]
+       adventofcode_2022_rust verify
+       adventofcode_2022_rust readme [--check]
+       adventofcode_2022_rust new-day <N> [--title <TITLE>]
+       adventofcode_2022_rust generate --day <N> [--seed <S>] [--size <K>]
+       adventofcode_2022_rust lint [--day <DAYS>] [--input <PATH>] [--input-dir <DIR>]
+
+Commands:
+    verify               Check every day against the answers in src/data/answers.txt
+    readme               Rewrite the results block in README.md from a fresh run,
+                         or with --check fail if it is out of date
+    new-day              Create src/dayNN.rs, an empty src/data/dayNN.txt and example test
+                         stubs, and register the day; existing files are never overwritten
+    generate             Print a random but valid input for a day, the same for the same seed
+                         [default seed: 0, size: 100]; what size counts depends on the day
+    lint                 Check inputs for structural problems before solving them, such as
+                         misaligned crates or throws to missing monkeys, listing each with its
+                         line number
+
+Options:
+    -d, --day <DAYS>     Day to run, either a single day (7) or a range (3..=9, 3..10)
+    -p, --part <PART>    Only run the given part (1 or 2)
+    -i, --input <PATH>   Read puzzle input from PATH instead of the input directory,
+                         or from stdin when PATH is -
+    -I, --input-dir <DIR>
+                         Read dayNN.txt inputs from DIR [default: $AOC_INPUT_DIR,
+                         or src/data in this crate, compiled in with embed-inputs]
+    -u, --users          Treat the input directory as one subdirectory of inputs per user
+                         (e.g. inputs/<user>/dayNN.txt) and run every set; days a user
+                         has no input for are skipped
+    -a, --all            Run every registered day (the default)
+    -s, --sequential     Solve days one after another instead of in parallel on up to one
+                         thread per core
+    -e, --example        Run the puzzle examples instead of the inputs and compare the
+                         answers with the ones given in the puzzle text
+    -w, --watch          Rerun whenever an input file (or the --input file) or a day's source
+                         changes, showing answers and timings (or example checks with
+                         --example) until interrupted; source changes are rebuilt first
+    -b, --bench          Time parsing and each part instead of printing answers (always sequential)
+    -m, --mem            Count allocations, bytes allocated and peak live bytes for parsing
+                         and each part instead of printing answers (always sequential)
+    -t, --timeout <TIME> Give each part at most TIME (e.g. 500ms, 2s or 1.5) and report it as
+                         timed out if it runs longer, carrying on with the other parts; a
+                         timed-out part can't be stopped and keeps running (and using a core)
+                         until the process exits
+        --impl <NAME>    Solve with the named implementation instead of the default one; every
+                         selected day and part must have one by that name
+    -c, --compare        Run every implementation of each selected part on the same input, check
+                         that they agree and time them against the default (always sequential)
+    -n, --iterations <N> Number of timed runs per stage when benchmarking or comparing
+                         [default: 10]
+    -f, --format <FMT>   Output format for answers, benchmarks, memory profiles and comparisons:
+                         text, json or csv [default: text]
+    -h, --help           Print this message";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(Options),
+    Verify,
+    Readme { check: bool },
+    NewDay { day: u8, title: Option<String> },
+    Generate { day: u8, seed: u64, size: usize },
+    Lint(Options),
+    Help,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Input {
+    File(PathBuf),
+    Stdin,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Options {
+    pub days: Vec<u8>,
+    pub parts: Vec<Part>,
+    pub input: Option<Input>,
+    pub input_dir: Option<PathBuf>,
+    pub users: bool,
+    pub sequential: bool,
+    pub bench: bool,
+    pub watch: bool,
+    pub example: bool,
+    pub mem: bool,
+    pub timeout: Option<Duration>,
+    pub implementation: Option<String>,
+    pub compare: bool,
+    pub iterations: usize,
+    pub format: Format,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            days: SOLUTIONS.iter().map(|solution| solution.day()).collect(),
+            parts: vec![Part::One, Part::Two],
+            input: None,
+            input_dir: None,
+            users: false,
+            sequential: false,
+            bench: false,
+            watch: false,
+            example: false,
+            mem: false,
+            timeout: None,
+            implementation: None,
+            compare: false,
+            iterations: 10,
+            format: Format::Text,
+        }
+    }
+}
+
+impl Options {
+    pub fn implementation(&self) -> &str {
+        self.implementation
+            .as_deref()
+            .unwrap_or(DEFAULT_IMPLEMENTATION)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct UsageError(String);
+
+impl Display for UsageError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+fn parse_day(value: &str) -> Result<u8, UsageError> {
+    value
+        .trim()
+        .parse::<u8>()
+        .map_err(|_| UsageError(format!("invalid day '{}'", value)))
+}
+
+fn parse_day_range(value: &str) -> Result<RangeInclusive<u8>, UsageError> {
+    if let Some((start, end)) = value.split_once("..=") {
+        Ok(parse_day(start)?..=parse_day(end)?)
+    } else if let Some((start, end)) = value.split_once("..") {
+        let end = parse_day(end)?;
+        if end == 0 {
+            return Err(UsageError(format!("empty day range '{}'", value)));
+        }
+        Ok(parse_day(start)?..=end - 1)
+    } else {
+        let day = parse_day(value)?;
+        Ok(day..=day)
+    }
+}
+
+fn parse_part(value: &str) -> Result<Part, UsageError> {
+    match value.trim() {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(UsageError(format!(
+            "invalid part '{}', expected 1 or 2",
+            value
+        ))),
+    }
+}
+
+fn parse_input(value: &str) -> Input {
+    match value {
+        "-" => Input::Stdin,
+        path => Input::File(PathBuf::from(path)),
+    }
+}
+
+fn parse_iterations(value: &str) -> Result<usize, UsageError> {
+    match value.trim().parse::<usize>() {
+        Ok(iterations) if iterations > 0 => Ok(iterations),
+        _ => Err(UsageError(format!(
+            "invalid iteration count '{}', expected a positive number",
+            value
+        ))),
+    }
+}
+
+// Accepts milliseconds ("500ms") or seconds ("2s", or just "2" or "1.5").
+fn parse_timeout(value: &str) -> Result<Duration, UsageError> {
+    let value = value.trim();
+    let seconds = match value.strip_suffix("ms") {
+        Some(millis) => millis.parse::<f64>().map(|millis| millis / 1000.0),
+        None => value.strip_suffix('s').unwrap_or(value).parse::<f64>(),
+    };
+
+    match seconds {
+        Ok(seconds) if seconds > 0.0 && seconds.is_finite() => Ok(Duration::from_secs_f64(seconds)),
+        _ => Err(UsageError(format!(
+            "invalid timeout '{}', expected a positive duration such as 500ms or 2s",
+            value
+        ))),
+    }
+}
+
+fn parse_format(value: &str) -> Result<Format, UsageError> {
+    match value.trim() {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        "csv" => Ok(Format::Csv),
+        _ => Err(UsageError(format!(
+            "invalid format '{}', expected text, json or csv",
+            value
+        ))),
+    }
+}
+
+fn resolve_days(range: RangeInclusive<u8>) -> Result<Vec<u8>, UsageError> {
+    if range.is_empty() {
+        return Err(UsageError(format!(
+            "empty day range {}..={}",
+            range.start(),
+            range.end()
+        )));
+    }
+
+    match range.clone().find(|day| get_solution(*day).is_none()) {
+        Some(day) => Err(UsageError(format!(
+            "day {} has no registered solution",
+            day
+        ))),
+        None => Ok(range.collect()),
+    }
+}
+
+fn parse_verify_args(mut args: impl Iterator<Item = String>) -> Result<Command, UsageError> {
+    match args.next() {
+        None => Ok(Command::Verify),
+        Some(arg) if arg == "-h" || arg == "--help" => Ok(Command::Help),
+        Some(arg) => Err(UsageError(format!("unexpected argument '{}'", arg))),
+    }
+}
+
+fn parse_readme_args(args: impl Iterator<Item = String>) -> Result<Command, UsageError> {
+    let mut check = false;
+    for arg in args {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-c" | "--check" => check = true,
+            _ => return Err(UsageError(format!("unexpected argument '{}'", arg))),
+        }
+    }
+    Ok(Command::Readme { check })
+}
+
+fn parse_new_day_args(mut args: impl Iterator<Item = String>) -> Result<Command, UsageError> {
+    let mut day: Option<u8> = None;
+    let mut title: Option<String> = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-t" | "--title" => {
+                title = Some(
+                    args.next()
+                        .ok_or_else(|| UsageError(format!("{} requires a value", arg)))?,
+                )
+            }
+            _ if day.is_none() => day = Some(parse_day(&arg)?),
+            _ => return Err(UsageError(format!("unexpected argument '{}'", arg))),
+        }
+    }
+
+    match day {
+        Some(day) if (1..=25).contains(&day) => Ok(Command::NewDay { day, title }),
+        Some(day) => Err(UsageError(format!("day {} is not an advent day", day))),
+        None => Err(UsageError(String::from("new-day requires a day"))),
+    }
+}
+
+fn parse_generate_args(mut args: impl Iterator<Item = String>) -> Result<Command, UsageError> {
+    let mut day: Option<u8> = None;
+    let mut seed: u64 = 0;
+    let mut size: usize = 100;
+    while let Some(arg) = args.next() {
+        let mut value_for = |flag: &str| {
+            args.next()
+                .ok_or_else(|| UsageError(format!("{} requires a value", flag)))
+        };
+
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-d" | "--day" => day = Some(parse_day(&value_for(&arg)?)?),
+            "--seed" => {
+                let value = value_for(&arg)?;
+                seed = value
+                    .trim()
+                    .parse::<u64>()
+                    .map_err(|_| UsageError(format!("invalid seed '{}'", value)))?
+            }
+            "--size" => {
+                let value = value_for(&arg)?;
+                size = value
+                    .trim()
+                    .parse::<usize>()
+                    .map_err(|_| UsageError(format!("invalid size '{}'", value)))?
+            }
+            _ => return Err(UsageError(format!("unexpected argument '{}'", arg))),
+        }
+    }
+
+    match day {
+        Some(day) => {
+            resolve_days(day..=day)?;
+            Ok(Command::Generate { day, seed, size })
+        }
+        None => Err(UsageError(String::from("generate requires a --day"))),
+    }
+}
+
+fn parse_lint_args(mut args: impl Iterator<Item = String>) -> Result<Command, UsageError> {
+    let mut days: Option<RangeInclusive<u8>> = None;
+    let mut input: Option<Input> = None;
+    let mut input_dir: Option<PathBuf> = None;
+    while let Some(arg) = args.next() {
+        let mut value_for = |flag: &str| {
+            args.next()
+                .ok_or_else(|| UsageError(format!("{} requires a value", flag)))
+        };
+
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-d" | "--day" => days = Some(parse_day_range(&value_for(&arg)?)?),
+            "-i" | "--input" => input = Some(parse_input(&value_for(&arg)?)),
+            "-I" | "--input-dir" => input_dir = Some(PathBuf::from(value_for(&arg)?)),
+            _ => return Err(UsageError(format!("unexpected argument '{}'", arg))),
+        }
+    }
+
+    let days = match days {
+        Some(range) => resolve_days(range)?,
+        None => SOLUTIONS.iter().map(|solution| solution.day()).collect(),
+    };
+
+    if input.is_some() && days.len() != 1 {
+        return Err(UsageError(String::from(
+            "--input can only be used with a single --day",
+        )));
+    }
+
+    if input.is_some() && input_dir.is_some() {
+        return Err(UsageError(String::from(
+            "--input cannot be used with --input-dir",
+        )));
+    }
+
+    Ok(Command::Lint(Options {
+        days,
+        input,
+        input_dir,
+        ..Options::default()
+    }))
+}
+
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Command, UsageError> {
+    let mut days: Option<RangeInclusive<u8>> = None;
+    let mut all = false;
+    let mut part: Option<Part> = None;
+    let mut input: Option<Input> = None;
+    let mut input_dir: Option<PathBuf> = None;
+    let mut users = false;
+    let mut sequential = false;
+    let mut bench = false;
+    let mut watch = false;
+    let mut example = false;
+    let mut mem = false;
+    let mut timeout: Option<Duration> = None;
+    let mut implementation: Option<String> = None;
+    let mut compare = false;
+    let mut iterations: usize = 10;
+    let mut format = Format::Text;
+
+    let mut args = args.into_iter().peekable();
+
+    match args.peek().map(String::as_str) {
+        Some("verify") => {
+            args.next();
+            return parse_verify_args(args);
+        }
+        Some("readme") => {
+            args.next();
+            return parse_readme_args(args);
+        }
+        Some("new-day") => {
+            args.next();
+            return parse_new_day_args(args);
+        }
+        Some("generate") => {
+            args.next();
+            return parse_generate_args(args);
+        }
+        Some("lint") => {
+            args.next();
+            return parse_lint_args(args);
+        }
+        _ => (),
+    }
+
+    while let Some(arg) = args.next() {
+        let mut value_for = |flag: &str| {
+            args.next()
+                .ok_or_else(|| UsageError(format!("{} requires a value", flag)))
+        };
+
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-a" | "--all" => all = true,
+            "-d" | "--day" => days = Some(parse_day_range(&value_for(&arg)?)?),
+            "-p" | "--part" => part = Some(parse_part(&value_for(&arg)?)?),
+            "-i" | "--input" => input = Some(parse_input(&value_for(&arg)?)),
+            "-I" | "--input-dir" => input_dir = Some(PathBuf::from(value_for(&arg)?)),
+            "-u" | "--users" => users = true,
+            "-s" | "--sequential" => sequential = true,
+            "-b" | "--bench" => bench = true,
+            "-w" | "--watch" => watch = true,
+            "-e" | "--example" => example = true,
+            "-m" | "--mem" => mem = true,
+            "-t" | "--timeout" => timeout = Some(parse_timeout(&value_for(&arg)?)?),
+            "--impl" => implementation = Some(value_for(&arg)?),
+            "-c" | "--compare" => compare = true,
+            "-n" | "--iterations" => iterations = parse_iterations(&value_for(&arg)?)?,
+            "-f" | "--format" => format = parse_format(&value_for(&arg)?)?,
+            _ => return Err(UsageError(format!("unexpected argument '{}'", arg))),
+        }
+    }
+
+    let days = match (days, all) {
+        (Some(_), true) => {
+            return Err(UsageError(String::from(
+                "--day and --all cannot be used together",
+            )))
+        }
+        (Some(range), false) => resolve_days(range)?,
+        (None, _) => SOLUTIONS.iter().map(|solution| solution.day()).collect(),
+    };
+
+    if input.is_some() && days.len() != 1 {
+        return Err(UsageError(String::from(
+            "--input can only be used with a single --day",
+        )));
+    }
+
+    if input.is_some() && (input_dir.is_some() || users) {
+        return Err(UsageError(String::from(
+            "--input cannot be used with --input-dir or --users",
+        )));
+    }
+
+    if users && bench {
+        return Err(UsageError(String::from(
+            "--users cannot be used with --bench",
+        )));
+    }
+
+    if watch && (bench || users || input == Some(Input::Stdin) || format != Format::Text) {
+        return Err(UsageError(String::from(
+            "--watch cannot be used with --bench, --users, stdin input or --format",
+        )));
+    }
+
+    if example
+        && (input.is_some() || input_dir.is_some() || users || bench || format != Format::Text)
+    {
+        return Err(UsageError(String::from(
+            "--example can only be used with --day, --part, --all and --watch",
+        )));
+    }
+
+    if mem && (bench || watch || example || users) {
+        return Err(UsageError(String::from(
+            "--mem cannot be used with --bench, --watch, --example or --users",
+        )));
+    }
+
+    if timeout.is_some() && (bench || mem || example) {
+        return Err(UsageError(String::from(
+            "--timeout cannot be used with --bench, --mem or --example",
+        )));
+    }
+
+    if compare && (bench || mem || watch || example || users || timeout.is_some()) {
+        return Err(UsageError(String::from(
+            "--compare cannot be used with --bench, --mem, --watch, --example, --users or --timeout",
+        )));
+    }
+
+    if compare && implementation.is_some() {
+        return Err(UsageError(String::from(
+            "--impl cannot be used with --compare, which runs every implementation",
+        )));
+    }
+
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![Part::One, Part::Two],
+    };
+
+    if let Some(name) = &implementation {
+        check_implementation(name, &days, &parts)?;
+    }
+
+    Ok(Command::Run(Options {
+        days,
+        parts,
+        input,
+        input_dir,
+        users,
+        sequential,
+        bench,
+        watch,
+        example,
+        mem,
+        timeout,
+        implementation,
+        compare,
+        iterations,
+        format,
+    }))
+}
+
+fn check_implementation(name: &str, days: &[u8], parts: &[Part]) -> Result<(), UsageError> {
+    for day in days {
+        let solution = get_solution(*day).ok_or(UsageError(format!(
+            "day {} has no registered solution",
+            day
+        )))?;
+        for part in parts {
+            if !solution.implementation_names(*part).contains(&name) {
+                return Err(UsageError(format!(
+                    "day {:02} part {} has no implementation named '{}'",
+                    day, part, name
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+fn resolve_input_dir(flag: Option<&Path>, var: Option<OsString>) -> Option<PathBuf> {
+    match (flag, var) {
+        (Some(dir), _) => Some(dir.to_path_buf()),
+        (None, Some(dir)) if !dir.is_empty() => Some(PathBuf::from(dir)),
+        _ => None,
+    }
+}
+
+// --input-dir wins over the environment variable, which wins over the crate's own inputs.
+pub fn input_dir(options: &Options) -> PathBuf {
+    resolve_input_dir(options.input_dir.as_deref(), env::var_os(INPUT_DIR_VAR))
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUT_DIR))
+}
+
+// The crate's own input for a day: compiled in when built with `embed-inputs`, otherwise
+// read from src/data.
+pub fn read_default_input(solution: &dyn Solution) -> Result<(String, String), String> {
+    match embedded::input(solution.day()) {
+        Some(input) => Ok((
+            format!("embedded day{:02}.txt", solution.day()),
+            normalize(input),
+        )),
+        None => {
+            let path = solution.input_path(Path::new(DEFAULT_INPUT_DIR));
+            Ok((path.display().to_string(), read_file(&path)?))
+        }
+    }
+}
+
+// Every subdirectory of `dir` is one user's input set, in name order.
+pub fn input_sets(dir: &Path) -> Result<Vec<String>, String> {
+    let entries =
+        read_dir(dir).map_err(|error| format!("could not read {}: {}", dir.display(), error))?;
+
+    let mut users: Vec<String> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect();
+    users.sort();
+
+    if users.is_empty() {
+        return Err(format!("{} has no input sets", dir.display()));
+    }
+    Ok(users)
+}
+
+// Returns a name for the input to use in diagnostics alongside its contents.
+pub fn read_input(options: &Options, solution: &dyn Solution) -> Result<(String, String), String> {
+    match &options.input {
+        Some(Input::Stdin) => Ok((String::from("stdin"), read_stdin()?)),
+        Some(Input::File(path)) => Ok((path.display().to_string(), read_file(path)?)),
+        None => match resolve_input_dir(options.input_dir.as_deref(), env::var_os(INPUT_DIR_VAR)) {
+            Some(dir) => {
+                let path = solution.input_path(&dir);
+                Ok((path.display().to_string(), read_file(&path)?))
+            }
+            None => read_default_input(solution),
+        },
+    }
+}
+
+fn read_user_jobs(
+    options: &Options,
+    solutions: &[&'static dyn Solution],
+) -> Result<Vec<Job>, String> {
+    let dir = input_dir(options);
+    let mut jobs: Vec<Job> = Vec::new();
+
+    for user in input_sets(&dir)? {
+        for solution in solutions {
+            let path = solution.input_path(&dir.join(&user));
+            if !path.exists() {
+                continue;
+            }
+            jobs.push(Job {
+                solution: *solution,
+                user: Some(user.clone()),
+                input_name: path.display().to_string(),
+                input: read_file(&path)?,
+            });
+        }
+    }
+
+    Ok(jobs)
+}
+
+pub fn read_jobs(options: &Options) -> Result<Vec<Job>, String> {
+    let solutions = options
+        .days
+        .iter()
+        .map(|day| get_solution(*day).ok_or(format!("day {} is not registered", day)))
+        .collect::<Result<Vec<&'static dyn Solution>, String>>()?;
+
+    if options.users {
+        return read_user_jobs(options, &solutions);
+    }
+
+    solutions
+        .into_iter()
+        .map(|solution| {
+            let (input_name, input) = read_input(options, solution)?;
+            Ok(Job {
+                solution,
+                user: None,
+                input_name,
+                input,
+            })
+        })
+        .collect()
+}
+
+pub fn run(options: &Options) -> Result<(), String> {
+    if options.bench {
+        return bench::run(options);
+    }
+    if options.mem {
+        return memory::run(options);
+    }
+    if options.watch {
+        return watch::run(options);
+    }
+    if options.example {
+        return examples::run(options);
+    }
+    if options.compare {
+        return compare::run(options);
+    }
+
+    let jobs = read_jobs(options)?;
+    let results = runner::solve(
+        jobs,
+        options.implementation(),
+        &options.parts,
+        !options.sequential,
+        options.timeout,
+    );
+
+    match options.format {
+        Format::Text => output::print_text(&results),
+        Format::Json => println!("{}", output::format_json(&results)),
+        Format::Csv => println!("{}", output::format_csv(&results)),
+    }
+
+    match output::failure_summary(&results) {
+        Some(message) => Err(message),
+        None => Ok(()),
+    }
+}
+
+// Inputs are normalized as they are read, so every day sees "\n" line endings whatever the
+// file was saved with.
+fn read_file(path: &Path) -> Result<String, String> {
+    read_to_string(path)
+        .map(|input| normalize(&input))
+        .map_err(|error| format!("could not read {}: {}", path.display(), error))
+}
+
+fn read_stdin() -> Result<String, String> {
+    let mut input = String::new();
+    io::stdin()
+        .read_to_string(&mut input)
+        .map_err(|error| format!("could not read stdin: {}", error))?;
+    Ok(normalize(&input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Command, UsageError> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    fn parse_options(args: &[&str]) -> Options {
+        match parse(args) {
+            Ok(Command::Run(options)) => options,
+            other => panic!("expected options, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn defaults_to_all_days_and_parts() {
+        let options = parse_options(&[]);
+        assert_eq!(options, Options::default());
+        assert_eq!(
+            options.days,
+            SOLUTIONS
+                .iter()
+                .map(|solution| solution.day())
+                .collect::<Vec<u8>>()
+        );
+        assert_eq!(options.parts, vec![Part::One, Part::Two]);
+        assert_eq!(options.input, None);
+        assert!(!options.sequential);
+        assert!(!options.bench);
+        assert_eq!(options.iterations, 10);
+        assert_eq!(options.format, Format::Text);
+        assert_eq!(parse_options(&["--all"]).days, options.days);
+    }
+
+    #[test]
+    fn single_day_and_part() {
+        let options = parse_options(&["--day", "7", "-p", "2"]);
+        assert_eq!(options.days, vec![7]);
+        assert_eq!(options.parts, vec![Part::Two]);
+        assert!(parse_options(&["--day", "7", "--sequential"]).sequential);
+    }
+
+    #[test]
+    fn day_ranges() {
+        assert_eq!(
+            parse_options(&["--day", "3..=9"]).days,
+            (3..=9).collect::<Vec<u8>>()
+        );
+        assert_eq!(
+            parse_options(&["-d", "3..9"]).days,
+            (3..=8).collect::<Vec<u8>>()
+        );
+    }
+
+    #[test]
+    fn input_path() {
+        let options = parse_options(&["--day", "9", "--input", "my.txt"]);
+        assert_eq!(options.input, Some(Input::File(PathBuf::from("my.txt"))));
+
+        let options = parse_options(&["--day", "9", "--input", "-"]);
+        assert_eq!(options.input, Some(Input::Stdin));
+    }
+
+    #[test]
+    fn input_dir_options() {
+        let options = parse_options(&["--input-dir", "inputs", "--users"]);
+        assert_eq!(options.input_dir, Some(PathBuf::from("inputs")));
+        assert!(options.users);
+
+        assert!(parse(&["-d", "1", "-i", "my.txt", "-I", "inputs"]).is_err());
+        assert!(parse(&["--users", "--bench"]).is_err());
+    }
+
+    #[test]
+    fn resolving_input_dir() {
+        let var = Some(OsString::from("from-env"));
+        assert_eq!(
+            resolve_input_dir(Some(Path::new("from-flag")), var.clone()),
+            Some(PathBuf::from("from-flag"))
+        );
+        assert_eq!(
+            resolve_input_dir(None, var),
+            Some(PathBuf::from("from-env"))
+        );
+        assert_eq!(resolve_input_dir(None, Some(OsString::new())), None);
+        assert_eq!(resolve_input_dir(None, None), None);
+    }
+
+    #[test]
+    fn reading_user_input_sets() {
+        let dir = env::temp_dir().join(format!("aoc-input-sets-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("bob")).unwrap();
+        std::fs::create_dir_all(dir.join("alice")).unwrap();
+        std::fs::write(dir.join("alice/day01.txt"), "1000\n\n2000").unwrap();
+        std::fs::write(dir.join("alice/day04.txt"), "2-4,6-8").unwrap();
+        std::fs::write(dir.join("bob/day04.txt"), "2-8,3-7").unwrap();
+
+        let options = Options {
+            days: vec![1, 4],
+            input_dir: Some(dir.clone()),
+            users: true,
+            ..Options::default()
+        };
+        let jobs = read_jobs(&options);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let jobs: Vec<(Option<String>, u8)> = jobs
+            .unwrap()
+            .iter()
+            .map(|job| (job.user.clone(), job.solution.day()))
+            .collect();
+        assert_eq!(
+            jobs,
+            vec![
+                (Some(String::from("alice")), 1),
+                (Some(String::from("alice")), 4),
+                (Some(String::from("bob")), 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn new_day_command() {
+        assert_eq!(
+            parse(&["new-day", "12"]),
+            Ok(Command::NewDay {
+                day: 12,
+                title: None
+            })
+        );
+        assert_eq!(
+            parse(&["new-day", "--title", "Hill Climbing Algorithm", "12"]),
+            Ok(Command::NewDay {
+                day: 12,
+                title: Some(String::from("Hill Climbing Algorithm"))
+            })
+        );
+        assert!(parse(&["new-day"]).is_err());
+        assert!(parse(&["new-day", "26"]).is_err());
+        assert!(parse(&["new-day", "12", "13"]).is_err());
+    }
+
+    #[test]
+    fn generate_command() {
+        assert_eq!(
+            parse(&["generate", "--day", "5", "--seed", "42", "--size", "1000"]),
+            Ok(Command::Generate {
+                day: 5,
+                seed: 42,
+                size: 1000
+            })
+        );
+        assert_eq!(
+            parse(&["generate", "-d", "8"]),
+            Ok(Command::Generate {
+                day: 8,
+                seed: 0,
+                size: 100
+            })
+        );
+        assert!(parse(&["generate"]).is_err());
+        assert!(parse(&["generate", "-d", "25"]).is_err());
+        assert!(parse(&["generate", "-d", "5", "--seed", "-1"]).is_err());
+    }
+
+    #[test]
+    fn lint_command() {
+        match parse(&["lint", "-d", "5", "--input", "my.txt"]) {
+            Ok(Command::Lint(options)) => {
+                assert_eq!(options.days, vec![5]);
+                assert_eq!(options.input, Some(Input::File(PathBuf::from("my.txt"))));
+            }
+            other => panic!("expected lint, got {:?}", other),
+        }
+        assert_eq!(parse(&["lint"]), Ok(Command::Lint(Options::default())));
+        assert!(parse(&["lint", "-d", "3..=5", "-i", "my.txt"]).is_err());
+        assert!(parse(&["lint", "--bench"]).is_err());
+    }
+
+    #[test]
+    fn watch_option() {
+        assert!(parse_options(&["--day", "5", "--watch"]).watch);
+        assert!(parse_options(&["-d", "5", "-w", "-i", "example.txt"]).watch);
+        let options = parse_options(&["-d", "5", "--watch", "--example"]);
+        assert!(options.watch && options.example);
+        assert!(parse(&["-d", "5", "-w", "-i", "-"]).is_err());
+        assert!(parse(&["-w", "--bench"]).is_err());
+        assert!(parse(&["-w", "-f", "json"]).is_err());
+    }
+
+    #[test]
+    fn example_option() {
+        assert!(parse_options(&["--day", "7", "--example"]).example);
+        assert!(parse_options(&["-e", "-p", "2"]).example);
+        assert!(parse(&["-d", "7", "-e", "-i", "my.txt"]).is_err());
+        assert!(parse(&["-e", "--bench"]).is_err());
+    }
+
+    #[test]
+    fn mem_option() {
+        let options = parse_options(&["--mem", "-d", "8", "-f", "csv"]);
+        assert!(options.mem);
+        assert_eq!(options.format, Format::Csv);
+        assert!(parse(&["--mem", "--bench"]).is_err());
+    }
+
+    #[test]
+    fn timeout_option() {
+        assert_eq!(
+            parse_options(&["--timeout", "500ms"]).timeout,
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(
+            parse_options(&["-t", "2s"]).timeout,
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(
+            parse_options(&["-t", "1.5"]).timeout,
+            Some(Duration::from_millis(1500))
+        );
+        assert!(parse(&["-t", "0"]).is_err());
+        assert!(parse(&["-t", "soon"]).is_err());
+        assert!(parse(&["-t", "1s", "--bench"]).is_err());
+    }
+
+    #[test]
+    fn impl_option() {
+        let options = parse_options(&["-d", "8", "--impl", "stack", "--bench"]);
+        assert_eq!(options.implementation(), "stack");
+        assert_eq!(Options::default().implementation(), DEFAULT_IMPLEMENTATION);
+        assert_eq!(
+            parse_options(&["-d", "3..=8", "--impl", "default"]).implementation(),
+            DEFAULT_IMPLEMENTATION
+        );
+        assert_eq!(
+            parse(&["-d", "3..=4", "--impl", "bitmask"]),
+            Err(UsageError(String::from(
+                "day 04 part 1 has no implementation named 'bitmask'"
+            )))
+        );
+        assert!(parse(&["--impl"]).is_err());
+    }
+
+    #[test]
+    fn compare_option() {
+        assert!(parse_options(&["--compare", "-d", "3", "-n", "5"]).compare);
+        assert!(parse_options(&["-c", "-f", "json"]).compare);
+        assert!(parse(&["-c", "--bench"]).is_err());
+        assert!(parse(&["-c", "-t", "1s"]).is_err());
+        assert!(parse(&["-c", "-d", "3", "--impl", "bitmask"]).is_err());
+    }
+
+    #[test]
+    fn help() {
+        assert_eq!(parse(&["--help"]), Ok(Command::Help));
+        assert_eq!(parse(&["--day", "1", "-h"]), Ok(Command::Help));
+    }
+
+    #[test]
+    fn bench_options() {
+        let options = parse_options(&["--bench", "-n", "50", "--format", "csv"]);
+        assert!(options.bench);
+        assert_eq!(options.iterations, 50);
+        assert_eq!(options.format, Format::Csv);
+    }
+
+    #[test]
+    fn output_format() {
+        assert_eq!(parse_options(&["-f", "json"]).format, Format::Json);
+        assert_eq!(parse_options(&["--format", "text"]).format, Format::Text);
+    }
+
+    #[test]
+    fn verify_command() {
+        assert_eq!(parse(&["verify"]), Ok(Command::Verify));
+        assert!(parse(&["verify", "--day", "1"]).is_err());
+    }
+
+    #[test]
+    fn readme_command() {
+        assert_eq!(parse(&["readme"]), Ok(Command::Readme { check: false }));
+        assert_eq!(
+            parse(&["readme", "--check"]),
+            Ok(Command::Readme { check: true })
+        );
+        assert!(parse(&["readme", "--day", "1"]).is_err());
+    }
+
+    #[test]
+    fn invalid_arguments() {
+        assert!(parse(&["--day"]).is_err());
+        assert!(parse(&["--day", "seven"]).is_err());
+        assert!(parse(&["--day", "9..3"]).is_err());
+        assert!(parse(&["--day", "26"]).is_err());
+        assert!(parse(&["--part", "3"]).is_err());
+        assert!(parse(&["--day", "1", "--all"]).is_err());
+        assert!(parse(&["--input", "my.txt"]).is_err());
+        assert!(parse(&["--all", "--input", "-"]).is_err());
+        assert!(parse(&["--frobnicate"]).is_err());
+        assert!(parse(&["--bench", "-n", "0"]).is_err());
+        assert!(parse(&["--format", "xml"]).is_err());
+    }
+}
@@ -0,0 +1,353 @@
+use std::hint::black_box;
+
+use crate::answer::Answer;
+use crate::bench::{format_duration, time, Stats};
+use crate::cli::{read_input, Format, Options};
+use crate::error::SolveError;
+use crate::output::{csv_field, json_answer, json_string};
+use crate::runner::solve_implementation_caught;
+use crate::solution::{get_solution, solve_named, Part, Solution};
+use crate::verify::summarize;
+
+#[derive(Debug, PartialEq)]
+pub struct Timing {
+    pub implementation: &'static str,
+    pub answer: Result<Answer, SolveError>,
+    // Only implementations that answer are timed.
+    pub stats: Option<Stats>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Comparison {
+    pub day: u8,
+    pub part: Part,
+    // The default implementation first, the one the others are timed against.
+    pub timings: Vec<Timing>,
+}
+
+impl Comparison {
+    pub fn agree(&self) -> bool {
+        let first = &self.timings[0].answer;
+        first.is_ok() && self.timings.iter().all(|timing| timing.answer == *first)
+    }
+
+    // The median time as a multiple of the default implementation's.
+    pub fn relative(&self, timing: &Timing) -> Option<f64> {
+        let baseline = self.timings[0].stats.as_ref()?.median;
+        let median = timing.stats.as_ref()?.median;
+        Some(median.as_secs_f64() / baseline.as_secs_f64().max(f64::MIN_POSITIVE))
+    }
+}
+
+// Runs every implementation of a part on the same input, then times the ones that answered.
+pub fn compare(solution: &dyn Solution, part: Part, input: &str, iterations: usize) -> Comparison {
+    let timings = solution
+        .implementation_names(part)
+        .into_iter()
+        .map(|implementation| {
+            let answer = solve_implementation_caught(solution, implementation, part, input);
+            let stats = answer.as_ref().ok().and_then(|_| {
+                time(iterations, || {
+                    solve_named(solution, implementation, part, black_box(input))
+                })
+                .ok()
+            });
+            Timing {
+                implementation,
+                answer,
+                stats,
+            }
+        })
+        .collect();
+
+    Comparison {
+        day: solution.day(),
+        part,
+        timings,
+    }
+}
+
+fn describe(answer: &Result<Answer, SolveError>) -> String {
+    match answer {
+        Ok(answer) => summarize(&answer.to_string()),
+        Err(error) => error.to_string(),
+    }
+}
+
+fn format_relative(relative: Option<f64>) -> String {
+    match relative {
+        Some(relative) => format!("{:.2}x", relative),
+        None => String::from("-"),
+    }
+}
+
+pub fn print_text(comparisons: &[Comparison]) {
+    println!(
+        "Day  Part  {:<16}  {:>10}  {:>8}  Answer",
+        "Implementation", "Median", "Relative"
+    );
+    for comparison in comparisons {
+        for timing in &comparison.timings {
+            let median = match &timing.stats {
+                Some(stats) => format_duration(stats.median),
+                None => String::from("-"),
+            };
+            println!(
+                "{:02}   {}     {:<16}  {:>10}  {:>8}  {}",
+                comparison.day,
+                comparison.part,
+                timing.implementation,
+                median,
+                format_relative(comparison.relative(timing)),
+                describe(&timing.answer)
+            );
+        }
+        if !comparison.agree() {
+            println!(
+                "Day {:02} part {}: the implementations disagree",
+                comparison.day, comparison.part
+            );
+        }
+    }
+}
+
+pub fn format_json(comparisons: &[Comparison]) -> String {
+    let records: Vec<String> = comparisons
+        .iter()
+        .flat_map(|comparison| {
+            comparison.timings.iter().map(move |timing| {
+                let outcome = match &timing.answer {
+                    Ok(answer) => format!("\"answer\":{}", json_answer(answer)),
+                    Err(error) => format!("\"error\":{}", json_string(&error.to_string())),
+                };
+                let median = match &timing.stats {
+                    Some(stats) => stats.median.as_nanos().to_string(),
+                    None => String::from("null"),
+                };
+                let relative = match comparison.relative(timing) {
+                    Some(relative) => format!("{:.4}", relative),
+                    None => String::from("null"),
+                };
+                format!(
+                    "  {{\"day\":{},\"part\":{},\"implementation\":{},{},\"median_ns\":{},\"relative\":{},\"agree\":{}}}",
+                    comparison.day,
+                    comparison.part,
+                    json_string(timing.implementation),
+                    outcome,
+                    median,
+                    relative,
+                    comparison.agree()
+                )
+            })
+        })
+        .collect();
+    format!("[\n{}\n]", records.join(",\n"))
+}
+
+pub fn format_csv(comparisons: &[Comparison]) -> String {
+    let mut lines = vec![String::from(
+        "day,part,implementation,answer,error,median_ns,relative,agree",
+    )];
+    for comparison in comparisons {
+        for timing in &comparison.timings {
+            let (answer, error) = match &timing.answer {
+                Ok(answer) => (answer.to_string(), String::new()),
+                Err(error) => (String::new(), error.to_string()),
+            };
+            lines.push(format!(
+                "{},{},{},{},{},{},{},{}",
+                comparison.day,
+                comparison.part,
+                csv_field(timing.implementation),
+                csv_field(&answer),
+                csv_field(&error),
+                timing
+                    .stats
+                    .as_ref()
+                    .map_or(String::new(), |stats| stats.median.as_nanos().to_string()),
+                comparison
+                    .relative(timing)
+                    .map_or(String::new(), |relative| format!("{:.4}", relative)),
+                comparison.agree()
+            ));
+        }
+    }
+    lines.join("\n")
+}
+
+// Only parts with more than one implementation are compared.
+pub fn run(options: &Options) -> Result<(), String> {
+    let mut comparisons: Vec<Comparison> = Vec::new();
+    for day in &options.days {
+        let solution = get_solution(*day).ok_or(format!("day {} is not registered", day))?;
+        let parts: Vec<Part> = options
+            .parts
+            .iter()
+            .copied()
+            .filter(|part| solution.implementation_names(*part).len() > 1)
+            .collect();
+        if parts.is_empty() {
+            continue;
+        }
+
+        let (_, input) = read_input(options, solution)?;
+        for part in parts {
+            comparisons.push(compare(solution, part, &input, options.iterations));
+        }
+    }
+
+    if comparisons.is_empty() {
+        return Err(String::from(
+            "none of the selected days has more than one implementation to compare",
+        ));
+    }
+
+    match options.format {
+        Format::Text => print_text(&comparisons),
+        Format::Json => println!("{}", format_json(&comparisons)),
+        Format::Csv => println!("{}", format_csv(&comparisons)),
+    }
+
+    match comparisons
+        .iter()
+        .filter(|comparison| !comparison.agree())
+        .count()
+    {
+        0 => Ok(()),
+        disagreeing => Err(format!("{} compared parts disagree", disagreeing)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::read_default_input;
+    use crate::day03::{self, Day03};
+    use crate::day08::Day08;
+    use crate::solution::{DEFAULT_IMPLEMENTATION, SOLUTIONS};
+    use std::time::Duration;
+
+    #[test]
+    fn implementations_agree_on_the_inputs() {
+        for solution in SOLUTIONS {
+            let (_, input) = read_default_input(*solution).unwrap();
+            for part in [Part::One, Part::Two] {
+                if solution.implementation_names(part).len() > 1 {
+                    let comparison = compare(*solution, part, &input, 1);
+                    assert!(comparison.agree(), "{:?}", comparison);
+                }
+            }
+        }
+    }
+
+    // Inputs some implementation used to answer while another panicked on them.
+    #[test]
+    fn implementations_fail_alike_on_malformed_inputs() {
+        let cases: [(&dyn Solution, Part, &str); 9] = [
+            (&Day03, Part::One, "abcd\n"),
+            (&Day03, Part::One, "abab\n"),
+            (&Day03, Part::One, "a\n"),
+            (&Day03, Part::Two, "aa\nbb\n"),
+            (&Day03, Part::Two, "ab\ncd\nef\n"),
+            (&Day03, Part::Two, "ab\nab\nab\n"),
+            (&Day08, Part::One, ""),
+            (&Day08, Part::Two, ""),
+            (&Day08, Part::Two, "12\n\n34\n"),
+        ];
+        for (solution, part, input) in cases {
+            let comparison = compare(solution, part, input, 1);
+            let first = &comparison.timings[0].answer;
+            assert!(
+                matches!(first, Err(SolveError::Parse(_))),
+                "{:?}",
+                comparison
+            );
+            for timing in &comparison.timings {
+                assert_eq!(timing.answer, *first, "{:?}", input);
+            }
+            for reference in solution.references() {
+                if reference.part == part {
+                    let answer = (reference.solve)(input).map_err(SolveError::Parse);
+                    assert_eq!(answer, *first, "{:?}", input);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn comparing_day03() {
+        let comparison = compare(&Day03, Part::Two, day03::EXAMPLE, 2);
+        let names: Vec<&str> = comparison
+            .timings
+            .iter()
+            .map(|timing| timing.implementation)
+            .collect();
+        assert_eq!(names, vec![DEFAULT_IMPLEMENTATION, "bitmask"]);
+        assert!(comparison.agree());
+        assert_eq!(comparison.relative(&comparison.timings[0]), Some(1.0));
+    }
+
+    fn comparison() -> Comparison {
+        let timing = |implementation, answer, median: Option<Duration>| Timing {
+            implementation,
+            answer,
+            stats: median.map(|median| Stats {
+                min: median,
+                median,
+                mean: median,
+                max: median,
+            }),
+        };
+        Comparison {
+            day: 8,
+            part: Part::One,
+            timings: vec![
+                timing(
+                    DEFAULT_IMPLEMENTATION,
+                    Ok(Answer::Integer(21)),
+                    Some(Duration::from_micros(400)),
+                ),
+                timing(
+                    "stack",
+                    Ok(Answer::Integer(20)),
+                    Some(Duration::from_micros(100)),
+                ),
+                timing(
+                    "broken",
+                    Err(SolveError::Panic {
+                        message: String::from("oops"),
+                        location: None,
+                    }),
+                    None,
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn disagreements_and_failures() {
+        let comparison = comparison();
+        assert!(!comparison.agree());
+        assert_eq!(comparison.relative(&comparison.timings[1]), Some(0.25));
+        assert_eq!(comparison.relative(&comparison.timings[2]), None);
+    }
+
+    #[test]
+    fn json_and_csv_output() {
+        assert_eq!(
+            format_json(&[comparison()]),
+            r#"[
+  {"day":8,"part":1,"implementation":"default","answer":21,"median_ns":400000,"relative":1.0000,"agree":false},
+  {"day":8,"part":1,"implementation":"stack","answer":20,"median_ns":100000,"relative":0.2500,"agree":false},
+  {"day":8,"part":1,"implementation":"broken","error":"panicked: oops","median_ns":null,"relative":null,"agree":false}
+]"#
+        );
+        assert_eq!(
+            format_csv(&[comparison()]),
+            "day,part,implementation,answer,error,median_ns,relative,agree
+8,1,default,21,,400000,1.0000,false
+8,1,stack,20,,100000,0.2500,false
+8,1,broken,,panicked: oops,,,false"
+        );
+    }
+}
@@ -1,35 +1,53 @@
-pub fn part_one(input: &str) -> u32 {
-    input
-        .split("\n\n")
+use crate::answer::Answer;
+use crate::error::{lines_before, ParseError};
+use crate::input::blocks;
+use crate::random::Rng;
+use crate::solution::{Example, Solution};
+
+const DAY: u8 = 1;
+
+pub fn parse(input: &str) -> Result<Vec<u32>, ParseError> {
+    blocks(input)
         .map(|elf| {
             elf.lines()
-                .map(|calorie| calorie.trim().parse::<u32>().unwrap())
-                .sum::<u32>()
+                .map(|line| {
+                    let calorie = line.trim();
+                    calorie.parse::<u32>().map_err(|_| {
+                        ParseError::new(DAY, line, calorie, "expected a calorie count")
+                            .on_line(lines_before(input, line) + 1)
+                    })
+                })
+                .sum::<Result<u32, ParseError>>()
         })
-        .max()
-        .unwrap()
+        .collect()
 }
 
-pub fn part_two(input: &str) -> u32 {
-    let mut calories_per_elf: Vec<u32> = input
-        .split("\n\n")
-        .map(|elf| {
-            elf.lines()
-                .map(|calorie| calorie.trim().parse::<u32>().unwrap())
-                .sum::<u32>()
-        })
-        .collect();
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    Ok(parse(input)?.into_iter().max().unwrap_or(0))
+}
+
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
+    let mut calories_per_elf: Vec<u32> = parse(input)?;
 
     calories_per_elf.sort_by(|a, b| b.cmp(a));
 
-    calories_per_elf.iter().take(3).sum()
+    Ok(calories_per_elf.iter().take(3).sum())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+// `size` elves carrying one to five snacks each.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let elves: Vec<String> = (0..size.max(1))
+        .map(|_| {
+            let snacks: Vec<String> = (0..rng.range(1..=5))
+                .map(|_| rng.range(1000..=60000).to_string())
+                .collect();
+            snacks.join("\n")
+        })
+        .collect();
+    elves.join("\n\n") + "\n"
+}
 
-    const INPUT: &str = "1000
+pub const EXAMPLE: &str = "1000
         2000
         3000
 
@@ -44,13 +62,60 @@ mod tests {
 
         10000";
 
+pub struct Day01;
+
+impl Solution for Day01 {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Calorie Counting"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_| ())
+    }
+
+    fn part_one(&self, input: &str) -> Result<Answer, ParseError> {
+        part_one(input).map(Answer::from)
+    }
+
+    fn part_two(&self, input: &str) -> Result<Answer, ParseError> {
+        part_two(input).map(Answer::from)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: EXAMPLE,
+            part_one: Some("24000"),
+            part_two: Some("45000"),
+        }]
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn part_one_works() {
-        assert_eq!(part_one(INPUT), 24000);
+        assert_eq!(part_one(EXAMPLE), Ok(24000));
     }
 
     #[test]
     fn part_two_works() {
-        assert_eq!(part_two(INPUT), 45000);
+        assert_eq!(part_two(EXAMPLE), Ok(45000));
+    }
+
+    #[test]
+    fn parse_error_location() {
+        let error = parse("1000\n2000\n\n3000\n4x00").unwrap_err();
+        assert_eq!((error.line, error.column), (5, 1));
+        assert_eq!(error.text, "4x00");
     }
 }
@@ -1,38 +1,86 @@
-#[derive(PartialEq, Debug)]
-enum Choice {
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::input::lines;
+use crate::random::Rng;
+use crate::solution::{Example, Solution};
+
+const DAY: u8 = 2;
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Choice {
     Rock,
     Paper,
     Scissors,
 }
 
-enum GameState {
+#[derive(PartialEq, Debug)]
+pub enum GameState {
     Win,
     Draw,
     Loss,
 }
 
-struct Game {
-    player_choice: Choice,
-    outcome: GameState,
+// The second column of the strategy guide, which each part interprets differently.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Strategy {
+    X,
+    Y,
+    Z,
 }
 
-impl Game {
-    pub fn from_part_one(game_arr: [&str; 2]) -> Game {
-        let opponent_choice: Choice = match game_arr[0] {
-            "A" => Choice::Rock,
-            "B" => Choice::Paper,
-            "C" => Choice::Scissors,
-            _ => panic!("Invalid game state"),
+#[derive(PartialEq, Debug)]
+pub struct Round {
+    pub opponent_choice: Choice,
+    pub strategy: Strategy,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct Game {
+    pub player_choice: Choice,
+    pub outcome: GameState,
+}
+
+impl TryFrom<&str> for Round {
+    type Error = ParseError;
+
+    fn try_from(line: &str) -> Result<Self, Self::Error> {
+        let mut columns = line.split_whitespace();
+
+        let opponent_choice = match columns.next() {
+            Some("A") => Choice::Rock,
+            Some("B") => Choice::Paper,
+            Some("C") => Choice::Scissors,
+            Some(code) => return Err(ParseError::new(DAY, line, code, "expected A, B or C")),
+            None => return Err(ParseError::missing(DAY, line, "expected A, B or C")),
+        };
+
+        let strategy = match columns.next() {
+            Some("X") => Strategy::X,
+            Some("Y") => Strategy::Y,
+            Some("Z") => Strategy::Z,
+            Some(code) => return Err(ParseError::new(DAY, line, code, "expected X, Y or Z")),
+            None => return Err(ParseError::missing(DAY, line, "expected X, Y or Z")),
         };
 
-        let player_choice: Choice = match game_arr[1] {
-            "X" => Choice::Rock,
-            "Y" => Choice::Paper,
-            "Z" => Choice::Scissors,
-            _ => panic!("Invalid game state"),
+        match columns.next() {
+            Some(extra) => Err(ParseError::new(DAY, line, extra, "expected end of line")),
+            None => Ok(Round {
+                opponent_choice,
+                strategy,
+            }),
+        }
+    }
+}
+
+impl Game {
+    pub fn from_part_one(round: &Round) -> Game {
+        let player_choice: Choice = match round.strategy {
+            Strategy::X => Choice::Rock,
+            Strategy::Y => Choice::Paper,
+            Strategy::Z => Choice::Scissors,
         };
 
-        let outcome = get_game_state(&opponent_choice, &player_choice);
+        let outcome = get_game_state(&round.opponent_choice, &player_choice);
 
         Game {
             player_choice,
@@ -40,22 +88,14 @@ impl Game {
         }
     }
 
-    pub fn from_part_two(game_arr: [&str; 2]) -> Game {
-        let opponent_choice: Choice = match game_arr[0] {
-            "A" => Choice::Rock,
-            "B" => Choice::Paper,
-            "C" => Choice::Scissors,
-            _ => panic!("Invalid game state"),
-        };
-
-        let outcome: GameState = match game_arr[1] {
-            "X" => GameState::Loss,
-            "Y" => GameState::Draw,
-            "Z" => GameState::Win,
-            _ => panic!("Invalid game state"),
+    pub fn from_part_two(round: &Round) -> Game {
+        let outcome: GameState = match round.strategy {
+            Strategy::X => GameState::Loss,
+            Strategy::Y => GameState::Draw,
+            Strategy::Z => GameState::Win,
         };
 
-        let player_choice = get_player_choice(opponent_choice, &outcome);
+        let player_choice = get_player_choice(round.opponent_choice, &outcome);
 
         Game {
             player_choice,
@@ -64,7 +104,7 @@ impl Game {
     }
 }
 
-fn get_player_choice(opponent_choice: Choice, outcome: &GameState) -> Choice {
+pub fn get_player_choice(opponent_choice: Choice, outcome: &GameState) -> Choice {
     match outcome {
         GameState::Draw => opponent_choice,
         GameState::Win => match opponent_choice {
@@ -80,7 +120,7 @@ fn get_player_choice(opponent_choice: Choice, outcome: &GameState) -> Choice {
     }
 }
 
-fn get_game_state(opponent_choice: &Choice, player_choice: &Choice) -> GameState {
+pub fn get_game_state(opponent_choice: &Choice, player_choice: &Choice) -> GameState {
     match (opponent_choice, player_choice) {
         (Choice::Rock, Choice::Paper)
         | (Choice::Paper, Choice::Scissors)
@@ -92,7 +132,7 @@ fn get_game_state(opponent_choice: &Choice, player_choice: &Choice) -> GameState
     }
 }
 
-fn get_game_score(game: Game) -> u32 {
+pub fn get_game_score(game: Game) -> u32 {
     let base_score = match game.outcome {
         GameState::Win => 6,
         GameState::Draw => 3,
@@ -108,56 +148,110 @@ fn get_game_score(game: Game) -> u32 {
     base_score + choice_score
 }
 
-pub fn part_one(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|game| {
-            let game_arr: [&str; 2] = game
-                .trim()
-                .split(' ')
-                .take(2)
-                .collect::<Vec<&str>>()
-                .try_into()
-                .unwrap();
-            Game::from_part_one(game_arr)
-        })
+pub fn parse(input: &str) -> Result<Vec<Round>, ParseError> {
+    lines(input)
+        .enumerate()
+        .map(|(index, line)| Round::try_from(line).map_err(|error| error.on_line(index + 1)))
+        .collect()
+}
+
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    Ok(parse(input)?
+        .iter()
+        .map(Game::from_part_one)
         .map(get_game_score)
-        .sum()
-}
-
-pub fn part_two(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|game| {
-            let game_arr: [&str; 2] = game
-                .trim()
-                .split(' ')
-                .take(2)
-                .collect::<Vec<&str>>()
-                .try_into()
-                .unwrap();
-            Game::from_part_two(game_arr)
-        })
+        .sum())
+}
+
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
+    Ok(parse(input)?
+        .iter()
+        .map(Game::from_part_two)
         .map(get_game_score)
-        .sum()
+        .sum())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+// `size` rounds of the strategy guide.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            format!(
+                "{} {}\n",
+                rng.choose(&['A', 'B', 'C']),
+                rng.choose(&['X', 'Y', 'Z'])
+            )
+        })
+        .collect()
+}
 
-    const INPUT: &str = "A Y
+pub const EXAMPLE: &str = "A Y
     B X
     C Z";
 
+pub struct Day02;
+
+impl Solution for Day02 {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Rock Paper Scissors"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_| ())
+    }
+
+    fn part_one(&self, input: &str) -> Result<Answer, ParseError> {
+        part_one(input).map(Answer::from)
+    }
+
+    fn part_two(&self, input: &str) -> Result<Answer, ParseError> {
+        part_two(input).map(Answer::from)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: EXAMPLE,
+            part_one: Some("15"),
+            part_two: Some("12"),
+        }]
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn part_one_works() {
-        assert_eq!(part_one(INPUT), 15);
+        assert_eq!(part_one(EXAMPLE), Ok(15));
     }
 
     #[test]
     fn part_two_works() {
-        assert_eq!(part_two(INPUT), 12);
+        assert_eq!(part_two(EXAMPLE), Ok(12));
+    }
+
+    #[test]
+    fn parsing_round() {
+        assert_eq!(
+            Round::try_from("C X"),
+            Ok(Round {
+                opponent_choice: Choice::Scissors,
+                strategy: Strategy::X
+            })
+        );
+
+        let error = parse("A Y\nB Q").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.text, "Q");
+        assert!(Round::try_from("A").is_err());
     }
 
     #[test]
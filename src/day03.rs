@@ -1,74 +1,295 @@
 use std::collections::HashSet;
 
-fn char_to_priority(c: char) -> u32 {
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::input::lines;
+use crate::random::Rng;
+use crate::solution::{Example, Implementation, Part, Solution};
+
+const DAY: u8 = 3;
+
+pub fn char_to_priority(c: char) -> Option<u32> {
     const RADIX: u32 = 36;
     match c {
-        x if x.is_ascii_uppercase() => x.to_digit(RADIX).unwrap() - 9 + 26,
-        x if x.is_ascii_lowercase() => x.to_digit(RADIX).unwrap() - 9,
-        _ => panic!("Invalid character supplied"),
+        x if x.is_ascii_uppercase() => Some(x.to_digit(RADIX)? - 9 + 26),
+        x if x.is_ascii_lowercase() => Some(x.to_digit(RADIX)? - 9),
+        _ => None,
     }
 }
 
-pub fn part_one(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|line| line.trim())
-        .map(|backpack| {
+// Each backpack is parsed into the priorities of the items it holds.
+pub fn parse(input: &str) -> Result<Vec<Vec<u32>>, ParseError> {
+    lines(input)
+        .enumerate()
+        .map(|(index, line)| {
+            let backpack = line.trim();
+            backpack
+                .char_indices()
+                .map(|(position, item)| {
+                    char_to_priority(item).ok_or_else(|| {
+                        let item_str = &backpack[position..position + item.len_utf8()];
+                        ParseError::new(DAY, line, item_str, "expected an item letter")
+                            .on_line(index + 1)
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// Each backpack's compartments must share exactly one item, and each group of three elves
+// exactly one badge. Every implementation reports the ones that don't with these, so they agree
+// on bad input as well as good.
+fn shared_items_error(input: &str, line_index: usize, shared: usize, place: &str) -> ParseError {
+    let line = lines(input).nth(line_index).unwrap_or(input);
+    let message = match shared {
+        0 => format!("expected an item in {}", place),
+        _ => format!("expected only one item in {}", place),
+    };
+    ParseError::new(DAY, line, line.trim(), message).on_line(line_index + 1)
+}
+
+fn compartments_error(input: &str, line_index: usize, shared: usize) -> ParseError {
+    shared_items_error(input, line_index, shared, "both compartments")
+}
+
+// Points at the first backpack of the group.
+fn badge_error(input: &str, group_index: usize, shared: usize) -> ParseError {
+    shared_items_error(
+        input,
+        group_index * 3,
+        shared,
+        "all three backpacks of the group",
+    )
+}
+
+fn incomplete_group_error(input: &str) -> ParseError {
+    let last_line = lines(input).last().unwrap_or(input);
+    ParseError::missing(DAY, last_line, "expected the group's third backpack")
+        .on_line(lines(input).count())
+}
+
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    parse(input)?
+        .iter()
+        .enumerate()
+        .map(|(index, backpack)| {
             let n_items_in_backpack = backpack.len();
             let (compart_1, compart_2) = backpack.split_at(n_items_in_backpack / 2);
-            (
-                compart_1.chars().collect::<HashSet<char>>(),
-                compart_2.chars().collect::<HashSet<char>>(),
-            )
-        })
-        .map(|(compart_1, compart_2)| {
-            char_to_priority(*compart_1.intersection(&compart_2).collect::<Vec<&char>>()[0])
+            let compart_1 = compart_1.iter().collect::<HashSet<&u32>>();
+            let compart_2 = compart_2.iter().collect::<HashSet<&u32>>();
+            match compart_1.intersection(&compart_2).collect::<Vec<_>>()[..] {
+                [item] => Ok(**item),
+                ref shared => Err(compartments_error(input, index, shared.len())),
+            }
         })
         .sum()
 }
 
-pub fn part_two(input: &str) -> u32 {
-    let lines: Vec<&str> = input.lines().map(|line| line.trim()).collect();
-    lines
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
+    parse(input)?
         .chunks(3)
-        .map(|elf_groups| {
-            let badge = elf_groups[0]
-                .chars()
-                .find(|item| elf_groups[1].contains(*item) && elf_groups[2].contains(*item))
-                .unwrap();
-            char_to_priority(badge)
+        .enumerate()
+        .map(|(index, elf_groups)| {
+            if elf_groups.len() < 3 {
+                return Err(incomplete_group_error(input));
+            }
+            let badges: HashSet<&u32> = elf_groups[0]
+                .iter()
+                .filter(|item| elf_groups[1].contains(item) && elf_groups[2].contains(item))
+                .collect();
+            match badges.into_iter().collect::<Vec<_>>()[..] {
+                [badge] => Ok(*badge),
+                ref shared => Err(badge_error(input, index, shared.len())),
+            }
         })
         .sum()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+// Both parts again with each backpack's items as bits of a u64 (priorities run up to 52), so
+// the shared item falls out of an `&` instead of building sets.
+pub mod bitmask {
+    use super::{badge_error, compartments_error, incomplete_group_error, parse};
+    use crate::error::ParseError;
+
+    fn mask(items: &[u32]) -> u64 {
+        items.iter().fold(0, |mask, priority| mask | 1 << priority)
+    }
+
+    // The priority of the only item in the mask, or how many there are if not exactly one.
+    fn priority(mask: u64) -> Result<u32, usize> {
+        match mask.count_ones() {
+            1 => Ok(mask.trailing_zeros()),
+            shared => Err(shared as usize),
+        }
+    }
+
+    pub fn part_one(input: &str) -> Result<u32, ParseError> {
+        parse(input)?
+            .iter()
+            .enumerate()
+            .map(|(index, backpack)| {
+                let (first, second) = backpack.split_at(backpack.len() / 2);
+                priority(mask(first) & mask(second))
+                    .map_err(|shared| compartments_error(input, index, shared))
+            })
+            .sum()
+    }
+
+    pub fn part_two(input: &str) -> Result<u32, ParseError> {
+        parse(input)?
+            .chunks(3)
+            .enumerate()
+            .map(|(index, group)| {
+                if group.len() < 3 {
+                    return Err(incomplete_group_error(input));
+                }
+                priority(
+                    group
+                        .iter()
+                        .fold(u64::MAX, |shared, elf| shared & mask(elf)),
+                )
+                .map_err(|shared| badge_error(input, index, shared))
+            })
+            .sum()
+    }
+}
+
+// `size` groups of three elves. Each elf packs from letters no other elf in the group uses, so
+// the compartments share exactly one item and the group exactly one badge.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut output = String::new();
+    for _ in 0..size.max(1) {
+        let mut letters: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+        rng.shuffle(&mut letters);
+        let (badge, letters) = letters.split_last().unwrap();
+
+        for pool in letters.chunks(letters.len() / 3) {
+            let (shared, pool) = pool.split_first().unwrap();
+            let (first_only, second_only) = pool.split_at(pool.len() / 2);
+            let half = rng.range(2..=12) as usize;
+
+            let mut first = vec![*shared, *badge];
+            first.extend((2..half).map(|_| *rng.choose(first_only)));
+            let mut second = vec![*shared];
+            second.extend((1..half).map(|_| *rng.choose(second_only)));
+            rng.shuffle(&mut first);
+            rng.shuffle(&mut second);
+
+            output.extend(first);
+            output.extend(second);
+            output.push('\n');
+        }
+    }
+    output
+}
 
-    const INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
+pub const EXAMPLE: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
   jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
   PmmdzqPrVvPwwTWBwg
   wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
   ttgJtRGJQctTZtZT
   CrZsJsPPZsGzwwsLwLmpwMDw";
 
+pub struct Day03;
+
+impl Solution for Day03 {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Rucksack Reorganization"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_| ())
+    }
+
+    fn part_one(&self, input: &str) -> Result<Answer, ParseError> {
+        part_one(input).map(Answer::from)
+    }
+
+    fn part_two(&self, input: &str) -> Result<Answer, ParseError> {
+        part_two(input).map(Answer::from)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: EXAMPLE,
+            part_one: Some("157"),
+            part_two: Some("70"),
+        }]
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn implementations(&self) -> &'static [Implementation] {
+        &[
+            Implementation {
+                name: "bitmask",
+                part: Part::One,
+                solve: |input| bitmask::part_one(input).map(Answer::from),
+            },
+            Implementation {
+                name: "bitmask",
+                part: Part::Two,
+                solve: |input| bitmask::part_two(input).map(Answer::from),
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn char_to_points() {
-        assert_eq!(char_to_priority('p'), 16);
-        assert_eq!(char_to_priority('L'), 38);
-        assert_eq!(char_to_priority('P'), 42);
-        assert_eq!(char_to_priority('v'), 22);
-        assert_eq!(char_to_priority('t'), 20);
-        assert_eq!(char_to_priority('s'), 19);
+        assert_eq!(char_to_priority('p'), Some(16));
+        assert_eq!(char_to_priority('L'), Some(38));
+        assert_eq!(char_to_priority('P'), Some(42));
+        assert_eq!(char_to_priority('v'), Some(22));
+        assert_eq!(char_to_priority('t'), Some(20));
+        assert_eq!(char_to_priority('s'), Some(19));
+        assert_eq!(char_to_priority('7'), None);
     }
 
     #[test]
     fn part_one_works() {
-        assert_eq!(part_one(INPUT), 157);
+        assert_eq!(part_one(EXAMPLE), Ok(157));
     }
 
     #[test]
     fn part_two_works() {
-        assert_eq!(part_two(INPUT), 70);
+        assert_eq!(part_two(EXAMPLE), Ok(70));
+    }
+
+    #[test]
+    fn bitmask_works() {
+        assert_eq!(bitmask::part_one(EXAMPLE), Ok(157));
+        assert_eq!(bitmask::part_two(EXAMPLE), Ok(70));
+    }
+
+    #[test]
+    fn parse_error_location() {
+        let error = parse("vJrwpWtwJgWrhcsFMMfFFhFp\n  jqHRNqR-qzjGDLGL").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 10));
+        assert_eq!(error.text, "-");
+    }
+
+    #[test]
+    fn backpacks_without_one_shared_item() {
+        let error = part_one("abab\nabcd").unwrap_err();
+        assert_eq!((error.line, error.text.as_str()), (1, "abab"));
+        assert_eq!(error.message, "expected only one item in both compartments");
+        let error = part_one("aa\nabcd").unwrap_err();
+        assert_eq!((error.line, error.text.as_str()), (2, "abcd"));
+        assert_eq!(error.message, "expected an item in both compartments");
+        let error = part_two("ab\nbc\nbd\nab\ncd").unwrap_err();
+        assert_eq!(error.line, 5);
+        assert_eq!(error.message, "expected the group's third backpack");
     }
 }
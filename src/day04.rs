@@ -1,66 +1,183 @@
 use std::ops::RangeInclusive;
 
-type Ranges = (RangeInclusive<u32>, RangeInclusive<u32>);
-
-fn get_ranges(range_pair: &str) -> Ranges {
-    let mut ranges = range_pair
-        .trim()
-        .split(',')
-        .map(|range| {
-            let mut range_iter = range
-                .split('-')
-                .map(|val| val.parse::<u32>().unwrap())
-                .take(2);
-            RangeInclusive::new(range_iter.next().unwrap(), range_iter.next().unwrap())
-        })
-        .take(2);
-    (ranges.next().unwrap(), ranges.next().unwrap())
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::input::lines;
+use crate::random::Rng;
+use crate::solution::{Example, Part, Reference, Solution};
+
+const DAY: u8 = 4;
+
+pub type Ranges = (RangeInclusive<u32>, RangeInclusive<u32>);
+
+pub fn get_ranges(range_pair: &str) -> Result<Ranges, ParseError> {
+    let mut ranges = range_pair.trim().split(',').map(|range| {
+        let mut range_iter = range.split('-').map(|val| {
+            val.parse::<u32>()
+                .map_err(|_| ParseError::new(DAY, range_pair, val, "expected a section number"))
+        });
+        match (range_iter.next(), range_iter.next(), range_iter.next()) {
+            (Some(start), Some(end), None) => Ok(RangeInclusive::new(start?, end?)),
+            _ => Err(ParseError::new(
+                DAY,
+                range_pair,
+                range,
+                "expected a range like 2-4",
+            )),
+        }
+    });
+    match (ranges.next(), ranges.next(), ranges.next()) {
+        (Some(first), Some(second), None) => Ok((first?, second?)),
+        _ => Err(ParseError::new(
+            DAY,
+            range_pair,
+            range_pair.trim(),
+            "expected two comma separated ranges",
+        )),
+    }
 }
 
-fn is_fully_containing_range(input: &Ranges) -> bool {
+pub fn is_fully_containing_range(input: &Ranges) -> bool {
     input.0.clone().all(|section| input.1.contains(&section))
         || input.1.clone().all(|section| input.0.contains(&section))
 }
 
-fn is_overlapping_range(input: &Ranges) -> bool {
+pub fn is_overlapping_range(input: &Ranges) -> bool {
     input.0.clone().any(|section| input.1.contains(&section))
 }
 
-pub fn part_one(input: &str) -> u32 {
-    input
-        .lines()
-        .map(get_ranges)
+pub fn parse(input: &str) -> Result<Vec<Ranges>, ParseError> {
+    lines(input)
+        .enumerate()
+        .map(|(index, line)| get_ranges(line).map_err(|error| error.on_line(index + 1)))
+        .collect()
+}
+
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    Ok(parse(input)?
+        .into_iter()
         .filter(is_fully_containing_range)
-        .count() as u32
+        .count() as u32)
 }
 
-pub fn part_two(input: &str) -> u32 {
-    input
-        .lines()
-        .map(get_ranges)
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
+    Ok(parse(input)?
+        .into_iter()
         .filter(is_overlapping_range)
-        .count() as u32
+        .count() as u32)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+// Both parts again, expanding every assignment into the set of sections it covers.
+pub mod reference {
+    use std::collections::HashSet;
+
+    use super::{parse, Ranges};
+    use crate::error::ParseError;
+
+    fn sections((first, second): &Ranges) -> (HashSet<u32>, HashSet<u32>) {
+        (first.clone().collect(), second.clone().collect())
+    }
+
+    pub fn part_one(input: &str) -> Result<u32, ParseError> {
+        Ok(parse(input)?
+            .iter()
+            .map(sections)
+            .filter(|(first, second)| first.is_subset(second) || second.is_subset(first))
+            .count() as u32)
+    }
+
+    pub fn part_two(input: &str) -> Result<u32, ParseError> {
+        Ok(parse(input)?
+            .iter()
+            .map(sections)
+            .filter(|(first, second)| !first.is_disjoint(second))
+            .count() as u32)
+    }
+}
 
-    const INPUT: &str = "2-4,6-8
+// `size` pairs of section assignments between 1 and 99.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let assignment = |rng: &mut Rng| {
+        let start = rng.range(1..=99);
+        format!("{}-{}", start, rng.range(start..=99))
+    };
+    (0..size)
+        .map(|_| format!("{},{}\n", assignment(rng), assignment(rng)))
+        .collect()
+}
+
+pub const EXAMPLE: &str = "2-4,6-8
     2-3,4-5
     5-7,7-9
     2-8,3-7
     6-6,4-6
     2-6,4-8";
 
+pub struct Day04;
+
+impl Solution for Day04 {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Camp Cleanup"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_| ())
+    }
+
+    fn part_one(&self, input: &str) -> Result<Answer, ParseError> {
+        part_one(input).map(Answer::from)
+    }
+
+    fn part_two(&self, input: &str) -> Result<Answer, ParseError> {
+        part_two(input).map(Answer::from)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: EXAMPLE,
+            part_one: Some("2"),
+            part_two: Some("4"),
+        }]
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn references(&self) -> &'static [Reference] {
+        &[
+            Reference {
+                part: Part::One,
+                solve: |input| reference::part_one(input).map(Answer::from),
+                max_size: 500,
+                max_cases: None,
+            },
+            Reference {
+                part: Part::Two,
+                solve: |input| reference::part_two(input).map(Answer::from),
+                max_size: 500,
+                max_cases: None,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn part_one_works() {
-        assert_eq!(part_one(INPUT), 2)
+        assert_eq!(part_one(EXAMPLE), Ok(2))
     }
 
     #[test]
     fn part_two_works() {
-        assert_eq!(part_two(INPUT), 4)
+        assert_eq!(part_two(EXAMPLE), Ok(4))
     }
 
     #[test]
@@ -74,10 +191,21 @@ mod tests {
             ("2-6,4-8", (2..=6, 4..=8)),
         ];
         for (input, expected_output) in TEST_VALS {
-            assert_eq!(get_ranges(input), expected_output);
+            assert_eq!(get_ranges(input), Ok(expected_output));
         }
     }
 
+    #[test]
+    fn get_ranges_errors() {
+        let error = get_ranges("2-4,6-x").unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (7, "x"));
+
+        let error = parse("2-4,6-8\n2-3").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+
+        assert!(get_ranges("2-4-5,6-8").is_err());
+    }
+
     #[test]
     fn range_contains_other_range() {
         const ONE_CONTAINS_ALL_TWO_RANGE: Ranges = (1..=9, 4..=8);
@@ -1,51 +1,219 @@
-struct Stacks {
+use crate::answer::Answer;
+use crate::error::{column_of, lines_before, ParseError};
+use crate::input::{blocks, lines};
+use crate::lint::Problem;
+use crate::random::Rng;
+use crate::solution::{Example, Solution};
+
+const DAY: u8 = 5;
+
+#[derive(Debug, PartialEq)]
+pub struct Stacks {
     stacks: Vec<Vec<char>>,
 }
 
-struct Instruction {
-    from: usize,
-    to: usize,
-    repeat: usize,
+#[derive(Debug, PartialEq)]
+pub struct Instruction {
+    pub from: usize,
+    pub to: usize,
+    pub repeat: usize,
+}
+
+// A number as it was written in a move.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Written {
+    pub column: usize,
+    pub text: String,
+}
+
+// An instruction with where it was written, so a move the stacks can't carry out is reported
+// at the number that is wrong.
+#[derive(Debug, PartialEq)]
+pub struct Move {
+    pub instruction: Instruction,
+    pub line: usize,
+    pub repeat: Written,
+    pub from: Written,
+    pub to: Written,
+}
+
+impl Written {
+    fn error(&self, line: usize, message: impl Into<String>) -> ParseError {
+        ParseError {
+            day: DAY,
+            line,
+            column: self.column,
+            text: self.text.clone(),
+            message: message.into(),
+        }
+    }
+}
+
+impl Move {
+    pub fn on_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
 }
 
-impl From<&str> for Stacks {
-    fn from(input: &str) -> Self {
-        let mut input_iter = input.lines().rev();
-        let n_stacks: usize = input_iter.next().unwrap().trim().split("   ").count();
+impl TryFrom<&str> for Stacks {
+    type Error = ParseError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        let drawing: Vec<&str> = lines(input).collect();
+        let (footer, rows) = match drawing.split_last() {
+            Some(split) => split,
+            None => return Err(ParseError::missing(DAY, input, "expected a stack drawing")),
+        };
 
         let mut stacks: Vec<Vec<char>> = Vec::new();
 
-        for _ in 0..n_stacks {
+        for (index, label) in footer.split_whitespace().enumerate() {
+            if label.parse::<usize>() != Ok(index + 1) {
+                return Err(ParseError::new(
+                    DAY,
+                    footer,
+                    label,
+                    format!("expected stack number {}", index + 1),
+                )
+                .on_line(drawing.len()));
+            }
             let empty_stack: Vec<char> = Vec::new();
             stacks.push(empty_stack);
         }
 
-        for input_row in input_iter {
-            for (stack_number, item) in input_row.chars().skip(1).step_by(4).enumerate() {
+        for (row_index, input_row) in rows.iter().enumerate().rev() {
+            for (stack_number, (position, item)) in
+                input_row.char_indices().skip(1).step_by(4).enumerate()
+            {
                 if item != ' ' {
-                    stacks[stack_number].push(item)
+                    match stacks.get_mut(stack_number) {
+                        Some(stack) => stack.push(item),
+                        None => {
+                            let item_str = &input_row[position..position + item.len_utf8()];
+                            return Err(ParseError::new(
+                                DAY,
+                                input_row,
+                                item_str,
+                                "expected crates to sit above a numbered stack",
+                            )
+                            .on_line(row_index + 1));
+                        }
+                    }
                 }
             }
         }
 
-        Stacks { stacks }
+        Ok(Stacks { stacks })
+    }
+}
+
+impl TryFrom<&str> for Instruction {
+    type Error = ParseError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        Move::try_from(input).map(|parsed| parsed.instruction)
     }
 }
 
-impl From<&str> for Instruction {
-    fn from(input: &str) -> Self {
-        // Assume values always come in format: "move A from B to C"
-        let mut input_values = input.split_whitespace().skip(1).step_by(2);
+impl TryFrom<&str> for Move {
+    type Error = ParseError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        // Values always come in format: "move A from B to C"
+        let mut words = input.split_whitespace();
+
+        let mut number_after = |keyword: &str| {
+            match words.next() {
+                Some(word) if word == keyword => (),
+                Some(word) => {
+                    return Err(ParseError::new(
+                        DAY,
+                        input,
+                        word,
+                        format!("expected '{}'", keyword),
+                    ))
+                }
+                None => {
+                    return Err(ParseError::missing(
+                        DAY,
+                        input,
+                        format!("expected '{}'", keyword),
+                    ))
+                }
+            }
+            match words.next() {
+                Some(value) => match value.parse::<usize>() {
+                    Ok(number) => Ok((number, value)),
+                    Err(_) => Err(ParseError::new(DAY, input, value, "expected a number")),
+                },
+                None => Err(ParseError::missing(DAY, input, "expected a number")),
+            }
+        };
+
+        let written = |value: &str| Written {
+            column: column_of(input, value),
+            text: value.to_owned(),
+        };
+        let stack_index = |(number, value): (usize, &str)| {
+            number
+                .checked_sub(1)
+                .map(|index| (index, written(value)))
+                .ok_or_else(|| ParseError::new(DAY, input, value, "stacks are numbered from 1"))
+        };
 
-        Instruction {
-            repeat: input_values.next().unwrap().parse().unwrap(),
-            from: input_values.next().unwrap().parse::<usize>().unwrap() - 1,
-            to: input_values.next().unwrap().parse::<usize>().unwrap() - 1,
+        let (repeat, repeat_value) = number_after("move")?;
+        let (from, from_written) = stack_index(number_after("from")?)?;
+        let (to, to_written) = stack_index(number_after("to")?)?;
+
+        match words.next() {
+            Some(extra) => Err(ParseError::new(DAY, input, extra, "expected end of line")),
+            None => Ok(Move {
+                instruction: Instruction { from, to, repeat },
+                line: 1,
+                repeat: written(repeat_value),
+                from: from_written,
+                to: to_written,
+            }),
         }
     }
 }
 
 impl Stacks {
+    pub fn stacks(&self) -> &[Vec<char>] {
+        &self.stacks
+    }
+
+    // Moves are checked against the stacks as they stand before being applied, since the
+    // parser alone can't tell whether a stack exists or holds enough crates.
+    pub fn check_move(&self, parsed: &Move) -> Result<(), ParseError> {
+        let instruction = &parsed.instruction;
+        for (stack, written) in [
+            (instruction.from, &parsed.from),
+            (instruction.to, &parsed.to),
+        ] {
+            if stack >= self.stacks.len() {
+                return Err(written.error(
+                    parsed.line,
+                    format!("expected a stack from 1 to {}", self.stacks.len()),
+                ));
+            }
+        }
+
+        let held = self.stacks[instruction.from].len();
+        if instruction.repeat > held {
+            return Err(parsed.repeat.error(
+                parsed.line,
+                format!(
+                    "expected at most {} crates, stack {} holds no more",
+                    held,
+                    instruction.from + 1
+                ),
+            ));
+        }
+        Ok(())
+    }
+
     pub fn apply_instruction_part_one(&mut self, instruction: Instruction) {
         for _ in 0..instruction.repeat {
             let moved_value = self.stacks[instruction.from].pop().unwrap();
@@ -74,37 +242,237 @@ impl Stacks {
     }
 }
 
-pub fn part_one(input: &str) -> String {
-    let parsed_input = input.split("\n\n").collect::<Vec<&str>>();
+pub fn parse(input: &str) -> Result<(Stacks, Vec<Move>), ParseError> {
+    let mut sections = blocks(input);
+    let (drawing, moves) = match (sections.next(), sections.next()) {
+        (Some(drawing), Some(moves)) => (drawing, moves),
+        _ => {
+            let last_line = lines(input).last().unwrap_or(input);
+            return Err(ParseError::missing(
+                DAY,
+                last_line,
+                "expected a blank line between the drawing and the moves",
+            )
+            .on_line(lines(input).count().max(1)));
+        }
+    };
+    if let Some(extra) = sections.next() {
+        let line = lines(extra).next().unwrap_or(extra);
+        return Err(
+            ParseError::new(DAY, line, line, "expected the moves to end")
+                .on_line(lines_before(input, extra) + 1),
+        );
+    }
+
+    let stacks = Stacks::try_from(drawing)?;
 
-    let mut stacks = Stacks::from(parsed_input[0]);
+    let moves_line_offset = lines_before(input, moves);
+    let instructions = lines(moves)
+        .enumerate()
+        .map(|(index, line)| {
+            let line_number = moves_line_offset + index + 1;
+            Move::try_from(line)
+                .map(|parsed| parsed.on_line(line_number))
+                .map_err(|error| error.on_line(line_number))
+        })
+        .collect::<Result<Vec<Move>, ParseError>>()?;
 
-    for instruction_line in parsed_input[1].lines() {
-        let instruction = Instruction::from(instruction_line);
-        stacks.apply_instruction_part_one(instruction);
+    Ok((stacks, instructions))
+}
+
+fn rearrange(input: &str, apply: fn(&mut Stacks, Instruction)) -> Result<String, ParseError> {
+    let (mut stacks, moves) = parse(input)?;
+
+    for parsed in moves {
+        stacks.check_move(&parsed)?;
+        apply(&mut stacks, parsed.instruction);
     }
 
-    stacks.get_top_of_stacks()
+    Ok(stacks.get_top_of_stacks())
 }
 
-pub fn part_two(input: &str) -> String {
-    let parsed_input = input.split("\n\n").collect::<Vec<&str>>();
+pub fn part_one(input: &str) -> Result<String, ParseError> {
+    rearrange(input, Stacks::apply_instruction_part_one)
+}
 
-    let mut stacks = Stacks::from(parsed_input[0]);
+pub fn part_two(input: &str) -> Result<String, ParseError> {
+    rearrange(input, Stacks::apply_instruction_part_two)
+}
 
-    for instruction_line in parsed_input[1].lines() {
-        let instruction = Instruction::from(instruction_line);
-        stacks.apply_instruction_part_two(instruction);
+// The footer's numbers must sit in the columns the parser reads stacks from, every crate above
+// one of them, and no move may take more crates than its stack holds at that point.
+pub fn lint(input: &str) -> Vec<Problem> {
+    let mut problems: Vec<Problem> = Vec::new();
+    // Without both sections there is nothing to check that the parser doesn't report.
+    let mut sections = blocks(input);
+    let (drawing, moves) = match (sections.next(), sections.next()) {
+        (Some(drawing), Some(moves)) => (drawing, moves),
+        _ => return problems,
+    };
+
+    let drawing_offset = lines_before(input, drawing);
+    let rows: Vec<&str> = lines(drawing).collect();
+    let (footer, rows) = match rows.split_last() {
+        Some(split) => split,
+        None => return problems,
+    };
+
+    // The 0-based character column each stack number starts in.
+    let labels: Vec<usize> = footer
+        .split_whitespace()
+        .map(|label| {
+            footer[..label.as_ptr() as usize - footer.as_ptr() as usize]
+                .chars()
+                .count()
+        })
+        .collect();
+    let mut heights: Vec<usize> = vec![0; labels.len()];
+    for (row_index, row) in rows.iter().enumerate() {
+        let line = drawing_offset + row_index + 1;
+        let items: Vec<char> = row.chars().collect();
+        let mut column = 0;
+        while column < items.len() {
+            match (items[column], items.get(column + 2)) {
+                (' ', _) => column += 1,
+                ('[', Some(']')) => {
+                    match labels.iter().position(|label| *label == column + 1) {
+                        Some(stack) => heights[stack] += 1,
+                        None => problems.push(Problem::new(
+                            DAY,
+                            line,
+                            format!(
+                                "crate [{}] in column {} is not above a stack number",
+                                items[column + 1],
+                                column + 2
+                            ),
+                        )),
+                    }
+                    column += 3;
+                }
+                (item, _) => {
+                    problems.push(Problem::new(
+                        DAY,
+                        line,
+                        format!(
+                            "expected a crate such as [A], found '{}' in column {}",
+                            item,
+                            column + 1
+                        ),
+                    ));
+                    column += 1;
+                }
+            }
+        }
+    }
+
+    for (index, column) in labels.iter().enumerate() {
+        if *column != 1 + 4 * index {
+            problems.push(Problem::new(
+                DAY,
+                drawing_offset + rows.len() + 1,
+                format!(
+                    "expected stack number {} in column {}, found it in column {}",
+                    index + 1,
+                    2 + 4 * index,
+                    column + 1
+                ),
+            ));
+        }
     }
 
-    stacks.get_top_of_stacks()
+    // Moves the parser rejects are left for it to report.
+    let moves_offset = lines_before(input, moves);
+    for (index, move_line) in lines(moves).enumerate() {
+        let Instruction { from, to, repeat } = match Instruction::try_from(move_line) {
+            Ok(instruction) => instruction,
+            Err(_) => continue,
+        };
+        let line = moves_offset + index + 1;
+        let missing: Vec<usize> = [from, to]
+            .into_iter()
+            .filter(|stack| *stack >= heights.len())
+            .collect();
+        if !missing.is_empty() {
+            for stack in missing {
+                problems.push(Problem::new(
+                    DAY,
+                    line,
+                    format!("there is no stack {}", stack + 1),
+                ));
+            }
+            continue;
+        }
+
+        if repeat > heights[from] {
+            problems.push(Problem::new(
+                DAY,
+                line,
+                format!(
+                    "moves {} crates from stack {}, which only holds {}",
+                    repeat,
+                    from + 1,
+                    heights[from]
+                ),
+            ));
+        }
+        let moved = repeat.min(heights[from]);
+        heights[from] -= moved;
+        heights[to] += moved;
+    }
+
+    problems
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+// Nine stacks of up to twelve crates and `size` moves (at least one), none of which takes more
+// crates than the stack holds at that point.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut stacks: Vec<Vec<char>> = (0..9)
+        .map(|_| {
+            (0..rng.range(0..=12))
+                .map(|_| char::from(b'A' + rng.below(26) as u8))
+                .collect()
+        })
+        .collect();
+    if stacks.iter().all(Vec::is_empty) {
+        stacks[0].push('A');
+    }
+
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+    let mut lines: Vec<String> = (0..height)
+        .rev()
+        .map(|level| {
+            let row: Vec<String> = stacks
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(item) => format!("[{}]", item),
+                    None => String::from("   "),
+                })
+                .collect();
+            row.join(" ")
+        })
+        .collect();
+    let footer: Vec<String> = (1..=stacks.len()).map(|n| format!(" {} ", n)).collect();
+    lines.push(footer.join(" "));
+    lines.push(String::new());
+
+    for _ in 0..size.max(1) {
+        let filled: Vec<usize> = (0..stacks.len())
+            .filter(|index| !stacks[*index].is_empty())
+            .collect();
+        let from = *rng.choose(&filled);
+        let to = (from + 1 + rng.index(stacks.len() - 1)) % stacks.len();
+        let count = rng.range(1..=stacks[from].len() as i64) as usize;
+
+        let remaining = stacks[from].len() - count;
+        let moved = stacks[from].split_off(remaining);
+        stacks[to].extend(moved);
+        lines.push(format!("move {} from {} to {}", count, from + 1, to + 1));
+    }
+
+    lines.join("\n") + "\n"
+}
 
-    const INPUT: &str = "    [D]
+pub const EXAMPLE: &str = "    [D]
 [N] [C]    
 [Z] [M] [P]
  1   2   3 
@@ -114,14 +482,58 @@ move 3 from 1 to 3
 move 2 from 2 to 1
 move 1 from 1 to 2";
 
+pub struct Day05;
+
+impl Solution for Day05 {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Supply Stacks"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_| ())
+    }
+
+    fn part_one(&self, input: &str) -> Result<Answer, ParseError> {
+        part_one(input).map(Answer::from)
+    }
+
+    fn part_two(&self, input: &str) -> Result<Answer, ParseError> {
+        part_two(input).map(Answer::from)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: EXAMPLE,
+            part_one: Some("CMZ"),
+            part_two: Some("MCD"),
+        }]
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn lint(&self, input: &str) -> Vec<Problem> {
+        lint(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn part_one_works() {
-        assert_eq!(part_one(INPUT), "CMZ")
+        assert_eq!(part_one(EXAMPLE), Ok(String::from("CMZ")))
     }
 
     #[test]
     fn part_two_works() {
-        assert_eq!(part_two(INPUT), "MCD")
+        assert_eq!(part_two(EXAMPLE), Ok(String::from("MCD")))
     }
 
     #[test]
@@ -130,7 +542,7 @@ move 1 from 1 to 2";
 [N] [C]    
 [Z] [M] [P]
  1   2   3 ";
-        let stacks = Stacks::from(INPUT);
+        let stacks = Stacks::try_from(INPUT).unwrap();
         assert_eq!(stacks.stacks.len(), 3);
         assert_eq!(stacks.stacks[0], vec!['Z', 'N']);
         assert_eq!(stacks.stacks[1], vec!['M', 'C', 'D']);
@@ -145,9 +557,92 @@ move 1 from 1 to 2";
             repeat: 3,
         };
         const INPUT: &str = "move 3 from 1 to 2";
-        assert_eq!(Instruction::from(INPUT).from, EXPECTED.from);
-        assert_eq!(Instruction::from(INPUT).to, EXPECTED.to);
-        assert_eq!(Instruction::from(INPUT).repeat, EXPECTED.repeat);
+        let instruction = Instruction::try_from(INPUT).unwrap();
+        assert_eq!(instruction.from, EXPECTED.from);
+        assert_eq!(instruction.to, EXPECTED.to);
+        assert_eq!(instruction.repeat, EXPECTED.repeat);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = Instruction::try_from("move 3 from 0 to 2").unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (13, "0"));
+
+        let error = Instruction::try_from("move 3 onto 2").unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (8, "onto"));
+
+        let error = parse(&EXAMPLE.replace("move 2", "move two")).unwrap_err();
+        assert_eq!((error.line, error.column), (8, 6));
+
+        let error = parse(&format!("{}\n\nmove 1 from 1 to 2", EXAMPLE)).unwrap_err();
+        assert_eq!(
+            (error.line, error.message.as_str()),
+            (11, "expected the moves to end")
+        );
+
+        let error =
+            part_one(&EXAMPLE.replace("move 2 from 2 to 1", "move 2 from 2 to 4")).unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.message.as_str()),
+            (8, 18, "expected a stack from 1 to 3")
+        );
+
+        let error = part_two(&EXAMPLE.replace("move 3 from 1", "move 4 from 1")).unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (7, 6, "4"));
+        assert_eq!(
+            error.message,
+            "expected at most 3 crates, stack 1 holds no more"
+        );
+
+        let error = Stacks::try_from("[A] [B] [C]\n 1   2").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (1, 10, "C")
+        );
+    }
+
+    #[test]
+    fn linting_drawings_and_moves() {
+        let input = "    [D]
+[N]  [C]
+[Z] [M] [P]
+ 1   2  3
+
+move 3 from 1 to 3
+move 1 from 2 to 4
+move 2 from 3 to 1";
+        let problems: Vec<(usize, String)> = lint(input)
+            .into_iter()
+            .map(|problem| (problem.line, problem.message))
+            .collect();
+        assert_eq!(
+            problems,
+            vec![
+                (
+                    2,
+                    String::from("crate [C] in column 7 is not above a stack number")
+                ),
+                (
+                    3,
+                    String::from("crate [P] in column 10 is not above a stack number")
+                ),
+                (
+                    4,
+                    String::from("expected stack number 3 in column 10, found it in column 9")
+                ),
+                (
+                    6,
+                    String::from("moves 3 crates from stack 1, which only holds 2")
+                ),
+                (7, String::from("there is no stack 4")),
+            ]
+        );
+
+        assert_eq!(
+            lint("[A] x\n 1\n\nmove 1 from 1 to 1")[0].message,
+            "expected a crate such as [A], found 'x' in column 5"
+        );
+        assert!(lint(EXAMPLE).is_empty());
     }
 
     #[test]
@@ -155,7 +650,7 @@ move 1 from 1 to 2";
         let mut test_stack: Stacks = Stacks {
             stacks: vec![vec!['A'], vec!['B']],
         };
-        let instruction: Instruction = Instruction::from("move 1 from 1 to 2");
+        let instruction: Instruction = Instruction::try_from("move 1 from 1 to 2").unwrap();
 
         test_stack.apply_instruction_part_one(instruction);
 
@@ -168,7 +663,7 @@ move 1 from 1 to 2";
         let mut test_stack: Stacks = Stacks {
             stacks: vec![vec!['A', 'B'], vec!['C']],
         };
-        let instruction: Instruction = Instruction::from("move 2 from 1 to 2");
+        let instruction: Instruction = Instruction::try_from("move 2 from 1 to 2").unwrap();
 
         test_stack.apply_instruction_part_two(instruction);
 
@@ -176,6 +671,35 @@ move 1 from 1 to 2";
         assert_eq!(test_stack.stacks[1], vec!['C', 'A', 'B']);
     }
 
+    #[test]
+    fn checking_moves_against_stacks() {
+        let stacks = Stacks {
+            stacks: vec![vec!['A'], vec!['B']],
+        };
+        let parsed = Move::try_from("move 1 from 1 to 2").unwrap().on_line(4);
+        assert_eq!(
+            parsed.from,
+            Written {
+                column: 13,
+                text: String::from("1")
+            }
+        );
+        assert_eq!(stacks.check_move(&parsed), Ok(()));
+
+        let error = stacks
+            .check_move(&Move::try_from("move  2 from 1 to 2").unwrap())
+            .unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (1, 7, "2"));
+
+        let error = stacks
+            .check_move(&Move::try_from("move 1 from 1 to 3").unwrap().on_line(9))
+            .unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (9, 18, "3")
+        );
+    }
+
     #[test]
     fn top_of_stacks_string() {
         let test_stack: Stacks = Stacks {
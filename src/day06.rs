@@ -1,9 +1,17 @@
 use std::collections::{HashSet, VecDeque};
 
-fn get_index_after_n_unique_chars(input: &str, n_unique_chars: usize) -> usize {
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::input::content;
+use crate::random::Rng;
+use crate::solution::{Example, Solution};
+
+const DAY: u8 = 6;
+
+pub fn get_index_after_n_unique_chars(signal: &[char], n_unique_chars: usize) -> usize {
     let mut last_n_chars: VecDeque<char> = VecDeque::new();
 
-    let input_iter = input.trim().chars().enumerate();
+    let input_iter = signal.iter().copied().enumerate();
 
     let mut index_after_n_unique_chars: usize = 0;
 
@@ -25,12 +33,88 @@ fn get_index_after_n_unique_chars(input: &str, n_unique_chars: usize) -> usize {
     index_after_n_unique_chars
 }
 
+pub fn parse(input: &str) -> Vec<char> {
+    content(input).trim_start().chars().collect()
+}
+
 pub fn part_one(input: &str) -> usize {
-    get_index_after_n_unique_chars(input, 4)
+    get_index_after_n_unique_chars(&parse(input), 4)
 }
 
 pub fn part_two(input: &str) -> usize {
-    get_index_after_n_unique_chars(input, 14)
+    get_index_after_n_unique_chars(&parse(input), 14)
+}
+
+// `size` characters that never hold four different letters in a row, a start-of-message marker
+// and `size` more random letters.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut signal: String = (0..size).map(|_| *rng.choose(&['a', 'b', 'c'])).collect();
+
+    let mut marker: Vec<char> = ('a'..='z').collect();
+    rng.shuffle(&mut marker);
+    signal.extend(&marker[..14]);
+
+    signal.extend((0..size).map(|_| char::from(b'a' + rng.below(26) as u8)));
+    signal + "\n"
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Tuning Trouble"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), ParseError> {
+        parse(input);
+        Ok(())
+    }
+
+    fn part_one(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(part_one(input).into())
+    }
+
+    fn part_two(&self, input: &str) -> Result<Answer, ParseError> {
+        Ok(part_two(input).into())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                input: "mjqjpqmgbljsphdztnvjfqwrcgsmlb",
+                part_one: Some("7"),
+                part_two: Some("19"),
+            },
+            Example {
+                input: "bvwbjplbgvbhsrlpgdmjqwftvncz",
+                part_one: Some("5"),
+                part_two: Some("23"),
+            },
+            Example {
+                input: "nppdvjthqldpwncqszvftbrmjlhg",
+                part_one: Some("6"),
+                part_two: Some("23"),
+            },
+            Example {
+                input: "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg",
+                part_one: Some("10"),
+                part_two: Some("29"),
+            },
+            Example {
+                input: "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw",
+                part_one: Some("11"),
+                part_two: Some("26"),
+            },
+        ]
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
 }
 
 #[cfg(test)]
@@ -1,8 +1,17 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::input::lines;
+use crate::lint::Problem;
+use crate::random::Rng;
+use crate::solution::{Example, Solution};
+
+const DAY: u8 = 7;
+
 #[derive(PartialEq, Debug)]
-enum ParseResult {
+pub enum ParseResult {
     ChangeDirectoryDown(String),
     ChangeDirectoryUp,
     ListDirectory,
@@ -10,49 +19,73 @@ enum ParseResult {
     DescendantDir(String),
 }
 
-impl From<&str> for ParseResult {
-    fn from(input: &str) -> Self {
+impl TryFrom<&str> for ParseResult {
+    type Error = ParseError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
         let mut input_iter = input.split_whitespace();
-        match input_iter.next().unwrap() {
-            "$" => {
-                if input_iter.next().unwrap() == "cd" {
-                    let arg: &str = input_iter.next().unwrap();
+        let mut next_word = |expected: &str| {
+            input_iter
+                .next()
+                .ok_or_else(|| ParseError::missing(DAY, input, format!("expected {}", expected)))
+        };
+
+        match next_word("a command or directory listing")? {
+            "$" => match next_word("a command")? {
+                "cd" => {
+                    let arg: &str = next_word("a directory name")?;
                     if arg == ".." {
-                        ParseResult::ChangeDirectoryUp
+                        Ok(ParseResult::ChangeDirectoryUp)
                     } else {
-                        ParseResult::ChangeDirectoryDown(arg.to_owned())
+                        Ok(ParseResult::ChangeDirectoryDown(arg.to_owned()))
                     }
-                } else {
-                    ParseResult::ListDirectory
                 }
-            }
-            "dir" => ParseResult::DescendantDir(input_iter.next().unwrap().to_owned()),
-            n => ParseResult::File {
-                name: input_iter.next().unwrap().to_owned(),
-                size: n.parse::<u32>().unwrap(),
+                "ls" => Ok(ParseResult::ListDirectory),
+                command => Err(ParseError::new(
+                    DAY,
+                    input,
+                    command,
+                    "expected 'cd' or 'ls'",
+                )),
             },
+            "dir" => Ok(ParseResult::DescendantDir(
+                next_word("a directory name")?.to_owned(),
+            )),
+            n => Ok(ParseResult::File {
+                size: n
+                    .parse::<u32>()
+                    .map_err(|_| ParseError::new(DAY, input, n, "expected a file size or 'dir'"))?,
+                name: next_word("a file name")?.to_owned(),
+            }),
         }
     }
 }
 
-fn get_dir_sizes(input: &str) -> HashMap<PathBuf, u32> {
+pub fn parse(input: &str) -> Result<Vec<ParseResult>, ParseError> {
+    lines(input)
+        .enumerate()
+        .map(|(index, line)| ParseResult::try_from(line).map_err(|error| error.on_line(index + 1)))
+        .collect()
+}
+
+pub fn get_dir_sizes(terminal_output: &[ParseResult]) -> HashMap<PathBuf, u32> {
     let mut current_path: PathBuf = Path::new("/").to_path_buf();
     let mut dir_sizes: HashMap<PathBuf, u32> = HashMap::new();
 
-    for line in input.lines() {
-        match ParseResult::from(line) {
+    for line in terminal_output {
+        match line {
             ParseResult::ListDirectory => {
                 dir_sizes.insert(current_path.clone(), 0);
             }
             ParseResult::ChangeDirectoryDown(directory) => {
-                current_path = append_dir(current_path, directory)
+                current_path = append_dir(current_path, directory.to_owned())
             }
             ParseResult::ChangeDirectoryUp => current_path = strip_last_dir(current_path),
             ParseResult::File { size, .. } => {
                 match dir_sizes.get_mut(&current_path) {
                     Some(volume) => *volume += size,
                     None => {
-                        dir_sizes.insert(current_path.clone(), size);
+                        dir_sizes.insert(current_path.clone(), *size);
                     }
                 };
 
@@ -80,33 +113,179 @@ fn strip_last_dir(path: PathBuf) -> PathBuf {
     }
 }
 
-pub fn part_one(input: &str) -> u32 {
-    let dir_sizes = get_dir_sizes(input);
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    let dir_sizes = get_dir_sizes(&parse(input)?);
 
-    dir_sizes
+    Ok(dir_sizes
         .values()
         .filter(|total_size| *total_size <= &100000)
-        .sum::<u32>()
+        .sum::<u32>())
 }
 
-pub fn part_two(input: &str) -> u32 {
-    let dir_sizes = get_dir_sizes(input);
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
+    let dir_sizes = get_dir_sizes(&parse(input)?);
 
-    let available_disk_space = 70000000 - *dir_sizes.get(&Path::new("/").to_path_buf()).unwrap();
-    let space_required = 30000000 - available_disk_space;
+    let used_disk_space = match dir_sizes.get(Path::new("/")) {
+        Some(size) => *size,
+        None => {
+            let last_line = lines(input).last().unwrap_or(input);
+            return Err(
+                ParseError::missing(DAY, last_line, "expected a listing of /")
+                    .on_line(lines(input).count().max(1)),
+            );
+        }
+    };
+    // What the update needs beyond the space already free, which is nothing on a disk with
+    // room to spare and more than the update itself on one that is overfull.
+    let space_required = (used_disk_space as u64 + 30000000).saturating_sub(70000000);
+    if space_required == 0 {
+        return Ok(0);
+    }
 
-    *dir_sizes
+    // / itself always frees enough.
+    Ok(dir_sizes
         .values()
-        .filter(|total_size| *total_size >= &space_required)
+        .copied()
+        .filter(|total_size| *total_size as u64 >= space_required)
         .min()
-        .unwrap()
+        .unwrap_or(used_disk_space))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+// Every `cd` must go into a directory an earlier `ls` of the current one listed, and never up
+// from /.
+pub fn lint(input: &str) -> Vec<Problem> {
+    let mut problems: Vec<Problem> = Vec::new();
+    let mut current_path: PathBuf = Path::new("/").to_path_buf();
+    let mut listed: HashMap<PathBuf, HashSet<String>> = HashMap::new();
+    let mut listing = false;
+
+    // Lines the parser rejects are left for it to report.
+    for (index, line) in lines(input).enumerate() {
+        let line_number = index + 1;
+        match ParseResult::try_from(line) {
+            Ok(ParseResult::ListDirectory) => {
+                listing = true;
+                listed.entry(current_path.clone()).or_default();
+            }
+            Ok(ParseResult::DescendantDir(directory)) if listing => {
+                listed
+                    .entry(current_path.clone())
+                    .or_default()
+                    .insert(directory);
+            }
+            Ok(ParseResult::ChangeDirectoryUp) => {
+                listing = false;
+                if current_path.parent().is_none() {
+                    problems.push(Problem::new(DAY, line_number, "cd .. from /"));
+                }
+                current_path = strip_last_dir(current_path);
+            }
+            Ok(ParseResult::ChangeDirectoryDown(directory)) => {
+                listing = false;
+                if directory != "/"
+                    && !listed
+                        .get(&current_path)
+                        .is_some_and(|directories| directories.contains(&directory))
+                {
+                    problems.push(Problem::new(
+                        DAY,
+                        line_number,
+                        format!(
+                            "cd into '{}', which no earlier ls of {} listed",
+                            directory,
+                            current_path.display()
+                        ),
+                    ));
+                }
+                current_path = append_dir(current_path, directory);
+            }
+            Ok(ParseResult::DescendantDir(_)) | Ok(ParseResult::File { .. }) if !listing => {
+                problems.push(Problem::new(
+                    DAY,
+                    line_number,
+                    "expected a listing to follow an ls",
+                ));
+            }
+            _ => (),
+        }
+    }
 
-    const INPUT: &str = "$ cd /
+    problems
+}
+
+fn directory_name(index: usize) -> String {
+    let mut name = String::new();
+    let mut index = index;
+    loop {
+        name.insert(0, char::from(b'a' + (index % 26) as u8));
+        index /= 26;
+        if index == 0 {
+            return name;
+        }
+    }
+}
+
+fn explore(
+    directory: usize,
+    children: &[Vec<usize>],
+    files: &[Vec<(String, u64)>],
+    transcript: &mut Vec<String>,
+) {
+    transcript.push(String::from("$ ls"));
+    for child in &children[directory] {
+        transcript.push(format!("dir {}", directory_name(*child)));
+    }
+    for (name, size) in &files[directory] {
+        transcript.push(format!("{} {}", size, name));
+    }
+
+    for child in &children[directory] {
+        transcript.push(format!("$ cd {}", directory_name(*child)));
+        explore(*child, children, files, transcript);
+        transcript.push(String::from("$ cd .."));
+    }
+}
+
+// A transcript exploring `size` directories besides /. File sizes are scaled so 45 to 65 million
+// of the disk is used, which leaves part two something to delete.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    // Directory 0 is /; every other directory hangs off one made before it.
+    let mut children: Vec<Vec<usize>> = vec![Vec::new(); size + 1];
+    for directory in 1..=size {
+        children[rng.index(directory)].push(directory);
+    }
+
+    let mut weights: Vec<Vec<u64>> = (0..=size)
+        .map(|_| (0..rng.range(0..=4)).map(|_| rng.below(1000) + 1).collect())
+        .collect();
+    if weights.iter().all(Vec::is_empty) {
+        weights[0].push(1);
+    }
+    let total_weight: u64 = weights.iter().flatten().sum();
+    let used = rng.range(45_000_000..=65_000_000) as u64;
+
+    let files: Vec<Vec<(String, u64)>> = weights
+        .iter()
+        .map(|weights| {
+            weights
+                .iter()
+                .map(|weight| {
+                    let name: String = (0..rng.range(1..=8))
+                        .map(|_| char::from(b'a' + rng.below(26) as u8))
+                        .collect();
+                    let extension = rng.choose(&["", ".txt", ".dat", ".log"]);
+                    (name + extension, (used * weight / total_weight).max(1))
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut transcript = vec![String::from("$ cd /")];
+    explore(0, &children, &files, &mut transcript);
+    transcript.join("\n") + "\n"
+}
+
+pub const EXAMPLE: &str = "$ cd /
 $ ls
 dir a
 14848514 b.txt
@@ -131,35 +310,145 @@ dir b
 5626152 d.ext
 7214296 k";
 
+pub struct Day07;
+
+impl Solution for Day07 {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "No Space Left On Device"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_| ())
+    }
+
+    fn part_one(&self, input: &str) -> Result<Answer, ParseError> {
+        part_one(input).map(Answer::from)
+    }
+
+    fn part_two(&self, input: &str) -> Result<Answer, ParseError> {
+        part_two(input).map(Answer::from)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: EXAMPLE,
+            part_one: Some("95437"),
+            part_two: Some("24933642"),
+        }]
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn lint(&self, input: &str) -> Vec<Problem> {
+        lint(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn part_one_works() {
-        assert_eq!(part_one(INPUT), 95437);
+        assert_eq!(part_one(EXAMPLE), Ok(95437));
     }
 
     #[test]
     fn part_two_works() {
-        assert_eq!(part_two(INPUT), 24933642)
+        assert_eq!(part_two(EXAMPLE), Ok(24933642))
+    }
+
+    #[test]
+    fn disks_without_a_root_or_out_of_room() {
+        let error = part_two("").unwrap_err();
+        assert_eq!(
+            (error.line, error.message.as_str()),
+            (1, "expected a listing of /")
+        );
+        let error = part_two("$ cd a\n$ ls\n10 f").unwrap_err();
+        assert_eq!(error.line, 3);
+
+        assert_eq!(part_two("$ cd /\n$ ls\n10 f"), Ok(0));
+        assert_eq!(
+            part_two("$ cd /\n$ ls\n40000000 f\ndir a\n$ cd a\n$ ls\n20000000 g"),
+            Ok(20000000)
+        );
+        assert_eq!(part_two("$ cd /\n$ ls\n90000000 f"), Ok(90000000));
     }
 
     #[test]
     fn parsing_line() {
-        assert_eq!(ParseResult::from("$ ls"), ParseResult::ListDirectory);
         assert_eq!(
-            ParseResult::from("$ cd /"),
-            ParseResult::ChangeDirectoryDown(String::from("/"))
+            ParseResult::try_from("$ ls"),
+            Ok(ParseResult::ListDirectory)
+        );
+        assert_eq!(
+            ParseResult::try_from("$ cd /"),
+            Ok(ParseResult::ChangeDirectoryDown(String::from("/")))
         );
-        assert_eq!(ParseResult::from("$ cd .."), ParseResult::ChangeDirectoryUp);
         assert_eq!(
-            ParseResult::from("dir a"),
-            ParseResult::DescendantDir(String::from("a"))
+            ParseResult::try_from("$ cd .."),
+            Ok(ParseResult::ChangeDirectoryUp)
         );
         assert_eq!(
-            ParseResult::from("12345 a.txt"),
-            ParseResult::File {
+            ParseResult::try_from("dir a"),
+            Ok(ParseResult::DescendantDir(String::from("a")))
+        );
+        assert_eq!(
+            ParseResult::try_from("12345 a.txt"),
+            Ok(ParseResult::File {
                 name: String::from("a.txt"),
                 size: 12345
-            }
+            })
+        );
+    }
+
+    #[test]
+    fn parsing_errors() {
+        let error = ParseResult::try_from("$ rm -rf").unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (3, "rm"));
+
+        let error = ParseResult::try_from("$ cd").unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (5, ""));
+
+        let error = parse("$ cd /\n$ ls\n12k b.txt").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+    }
+
+    #[test]
+    fn linting_directory_changes() {
+        let input = "$ cd /
+$ ls
+dir a
+14848514 b.txt
+$ cd a
+$ cd e
+$ cd ..
+$ cd ..
+$ cd ..
+123 stray.txt";
+        let problems: Vec<(usize, String)> = lint(input)
+            .into_iter()
+            .map(|problem| (problem.line, problem.message))
+            .collect();
+        assert_eq!(
+            problems,
+            vec![
+                (
+                    6,
+                    String::from("cd into 'e', which no earlier ls of /a listed")
+                ),
+                (9, String::from("cd .. from /")),
+                (10, String::from("expected a listing to follow an ls")),
+            ]
         );
+        assert!(lint(EXAMPLE).is_empty());
     }
 
     #[test]
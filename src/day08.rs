@@ -1,26 +1,61 @@
-struct Forest {
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::input::lines;
+use crate::lint::Problem;
+use crate::random::Rng;
+use crate::solution::{Example, Implementation, Part, Reference, Solution};
+
+const DAY: u8 = 8;
+
+#[derive(Debug, PartialEq)]
+pub struct Forest {
     width: u32,
     length: u32,
     rows: Vec<Vec<u8>>,
     columns: Vec<Vec<u8>>,
 }
 
-impl From<&str> for Forest {
-    fn from(input: &str) -> Self {
+impl TryFrom<&str> for Forest {
+    type Error = ParseError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
         let mut rows: Vec<Vec<u8>> = Vec::new();
         let mut columns: Vec<Vec<u8>> = Vec::new();
 
-        for line in input.lines() {
-            let heights: Vec<u8> = line
-                .trim()
-                .split("")
-                .filter_map(|char| char.parse::<u8>().ok())
-                .collect();
+        for (line_index, line) in lines(input).enumerate() {
+            let trimmed_line = line.trim();
+            let heights: Vec<u8> = trimmed_line
+                .char_indices()
+                .map(|(position, char)| match char.to_digit(10) {
+                    Some(height) => Ok(height as u8),
+                    None => Err(ParseError::new(
+                        DAY,
+                        line,
+                        &trimmed_line[position..position + char.len_utf8()],
+                        "expected a tree height",
+                    )
+                    .on_line(line_index + 1)),
+                })
+                .collect::<Result<Vec<u8>, ParseError>>()?;
 
+            if heights.is_empty() {
+                return Err(
+                    ParseError::missing(DAY, line, "expected a row of tree heights")
+                        .on_line(line_index + 1),
+                );
+            }
             if columns.is_empty() {
                 for _ in 0..heights.len() {
                     columns.push(Vec::new())
                 }
+            } else if heights.len() != columns.len() {
+                return Err(ParseError::new(
+                    DAY,
+                    line,
+                    trimmed_line,
+                    format!("expected a row of {} trees", columns.len()),
+                )
+                .on_line(line_index + 1));
             }
 
             heights
@@ -31,25 +66,53 @@ impl From<&str> for Forest {
             rows.push(heights);
         }
 
-        Forest {
+        if rows.is_empty() {
+            return Err(ParseError::missing(
+                DAY,
+                input,
+                "expected a forest of tree heights",
+            ));
+        }
+
+        Ok(Forest {
             width: columns.len() as u32,
             length: rows.len() as u32,
             rows,
             columns,
-        }
+        })
     }
 }
 
 impl Forest {
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn length(&self) -> u32 {
+        self.length
+    }
+
+    pub fn rows(&self) -> &[Vec<u8>] {
+        &self.rows
+    }
+
+    pub fn columns(&self) -> &[Vec<u8>] {
+        &self.columns
+    }
+
+    // A forest one tree long or wide is all perimeter.
     fn get_perimiter_tree_count(self) -> u32 {
+        if self.length < 2 || self.width < 2 {
+            return self.length * self.width;
+        }
         (self.length + self.width) * 2 - 4
     }
 
     pub fn get_visible_tree_count(self) -> u32 {
         let mut visible_tree_count: u32 = 0;
 
-        for row_index in 1..self.width - 1 {
-            for col_index in 1..self.length - 1 {
+        for row_index in 1..self.length.saturating_sub(1) {
+            for col_index in 1..self.width.saturating_sub(1) {
                 let current_tree_height = self.rows[row_index as usize][col_index as usize];
 
                 let current_tree_is_taller = |tree_height: &u8| tree_height < &current_tree_height;
@@ -83,8 +146,8 @@ impl Forest {
     pub fn get_max_scenic_score(self) -> u32 {
         let mut scenic_scores: Vec<u32> = Vec::new();
 
-        for row_index in 1..self.width - 1 {
-            for col_index in 1..self.length - 1 {
+        for row_index in 1..self.length.saturating_sub(1) {
+            for col_index in 1..self.width.saturating_sub(1) {
                 let current_tree_height = self.rows[row_index as usize][col_index as usize];
 
                 let tree_count_left = match self.rows[row_index as usize]
@@ -130,41 +193,354 @@ impl Forest {
             }
         }
 
-        *scenic_scores.iter().max().unwrap()
+        // Trees on the edge see nothing in at least one direction, so score 0.
+        scenic_scores.into_iter().max().unwrap_or(0)
     }
 }
 
-pub fn part_one(input: &str) -> u32 {
-    Forest::from(input).get_visible_tree_count()
+pub fn parse(input: &str) -> Result<Forest, ParseError> {
+    Forest::try_from(input)
 }
 
-pub fn part_two(input: &str) -> u32 {
-    Forest::from(input).get_max_scenic_score()
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    Ok(parse(input)?.get_visible_tree_count())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
+    Ok(parse(input)?.get_max_scenic_score())
+}
+
+// Both parts again, walking every line of sight from every tree on the grid.
+pub mod reference {
+    use super::parse;
+    use crate::error::ParseError;
+
+    const DIRECTIONS: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+    // The heights met walking from a tree to the edge, nearest first.
+    fn line_of_sight(
+        rows: &[Vec<u8>],
+        (row, column): (usize, usize),
+        (row_step, column_step): (isize, isize),
+    ) -> Vec<u8> {
+        let mut heights = Vec::new();
+        let (mut row, mut column) = (row as isize + row_step, column as isize + column_step);
+        while let Some(height) = usize::try_from(row)
+            .ok()
+            .zip(usize::try_from(column).ok())
+            .and_then(|(row, column)| rows.get(row)?.get(column))
+        {
+            heights.push(*height);
+            row += row_step;
+            column += column_step;
+        }
+        heights
+    }
+
+    fn trees(rows: &[Vec<u8>]) -> impl Iterator<Item = ((usize, usize), u8)> + '_ {
+        rows.iter().enumerate().flat_map(|(row, heights)| {
+            heights
+                .iter()
+                .enumerate()
+                .map(move |(column, height)| ((row, column), *height))
+        })
+    }
+
+    pub fn part_one(input: &str) -> Result<u32, ParseError> {
+        let forest = parse(input)?;
+        let rows = forest.rows();
+        Ok(trees(rows)
+            .filter(|(position, height)| {
+                DIRECTIONS.iter().any(|direction| {
+                    line_of_sight(rows, *position, *direction)
+                        .iter()
+                        .all(|other| other < height)
+                })
+            })
+            .count() as u32)
+    }
+
+    pub fn part_two(input: &str) -> Result<u32, ParseError> {
+        let forest = parse(input)?;
+        let rows = forest.rows();
+        Ok(trees(rows)
+            .map(|(position, height)| {
+                DIRECTIONS
+                    .iter()
+                    .map(|direction| {
+                        let heights = line_of_sight(rows, position, *direction);
+                        match heights.iter().position(|other| *other >= height) {
+                            Some(blocking) => blocking as u32 + 1,
+                            None => heights.len() as u32,
+                        }
+                    })
+                    .product()
+            })
+            .max()
+            .unwrap_or(0))
+    }
+}
+
+// Both parts in one pass along each row and column from either end instead of walking every
+// line of sight, keeping a stack of the trees nothing nearer has hidden yet.
+pub mod stack {
+    use super::parse;
+    use crate::error::ParseError;
+
+    // Whether each tree is visible from the start of the line and how far it sees back towards
+    // it. The stack's heights only ever decrease from bottom to top.
+    fn sweep(heights: &[u8]) -> Vec<(bool, u32)> {
+        let mut stack: Vec<usize> = Vec::new();
+        heights
+            .iter()
+            .enumerate()
+            .map(|(index, height)| {
+                while stack.last().is_some_and(|top| heights[*top] < *height) {
+                    stack.pop();
+                }
+                let view = match stack.last() {
+                    Some(blocking) => (index - blocking) as u32,
+                    None => index as u32,
+                };
+                let visible = stack.is_empty();
+                stack.push(index);
+                (visible, view)
+            })
+            .collect()
+    }
+
+    // Visible from either end, and the product of the views both ways.
+    fn both_ways(heights: &[u8]) -> Vec<(bool, u32)> {
+        let reversed: Vec<u8> = heights.iter().rev().copied().collect();
+        let mut backward = sweep(&reversed);
+        backward.reverse();
+        sweep(heights)
+            .into_iter()
+            .zip(backward)
+            .map(|((ahead, ahead_view), (behind, behind_view))| {
+                (ahead || behind, ahead_view * behind_view)
+            })
+            .collect()
+    }
+
+    fn views(input: &str) -> Result<Vec<(bool, u32)>, ParseError> {
+        let forest = parse(input)?;
+        let across: Vec<Vec<(bool, u32)>> =
+            forest.rows().iter().map(|row| both_ways(row)).collect();
+        let down: Vec<Vec<(bool, u32)>> = forest
+            .columns()
+            .iter()
+            .map(|column| both_ways(column))
+            .collect();
+
+        Ok(across
+            .iter()
+            .enumerate()
+            .flat_map(|(row, views)| {
+                let down = &down;
+                views
+                    .iter()
+                    .enumerate()
+                    .map(move |(column, (visible, view))| {
+                        let (visible_down, view_down) = down[column][row];
+                        (*visible || visible_down, view * view_down)
+                    })
+            })
+            .collect())
+    }
+
+    pub fn part_one(input: &str) -> Result<u32, ParseError> {
+        Ok(views(input)?.iter().filter(|(visible, _)| *visible).count() as u32)
+    }
+
+    pub fn part_two(input: &str) -> Result<u32, ParseError> {
+        Ok(views(input)?
+            .iter()
+            .map(|(_, score)| *score)
+            .max()
+            .unwrap_or(0))
+    }
+}
+
+// Every row must be as wide as the first; the parser stops at the first one that isn't.
+pub fn lint(input: &str) -> Vec<Problem> {
+    let widths: Vec<usize> = lines(input)
+        .map(|line| line.trim().chars().count())
+        .collect();
+    widths
+        .iter()
+        .enumerate()
+        .skip(1)
+        .filter(|(_, width)| **width != widths[0])
+        .map(|(index, width)| {
+            Problem::new(
+                DAY,
+                index + 1,
+                format!(
+                    "expected a row of {} trees like the first, found {}",
+                    widths[0], width
+                ),
+            )
+        })
+        .collect()
+}
+
+// A forest `size` trees wide (at least 3, so there are interior trees to score) and between 3
+// and twice that long, of random heights.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let width = size.max(3);
+    let length = rng.range(3..=2 * width as i64);
+    (0..length)
+        .map(|_| {
+            let mut row: String = (0..width)
+                .map(|_| char::from(b'0' + rng.below(10) as u8))
+                .collect();
+            row.push('\n');
+            row
+        })
+        .collect()
+}
 
-    const INPUT: &str = "30373
+pub const EXAMPLE: &str = "30373
 25512
 65332
 33549
 35390";
 
+pub struct Day08;
+
+impl Solution for Day08 {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Treetop Tree House"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_| ())
+    }
+
+    fn part_one(&self, input: &str) -> Result<Answer, ParseError> {
+        part_one(input).map(Answer::from)
+    }
+
+    fn part_two(&self, input: &str) -> Result<Answer, ParseError> {
+        part_two(input).map(Answer::from)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: EXAMPLE,
+            part_one: Some("21"),
+            part_two: Some("8"),
+        }]
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn lint(&self, input: &str) -> Vec<Problem> {
+        lint(input)
+    }
+
+    fn references(&self) -> &'static [Reference] {
+        &[
+            Reference {
+                part: Part::One,
+                solve: |input| reference::part_one(input).map(Answer::from),
+                max_size: 60,
+                max_cases: None,
+            },
+            Reference {
+                part: Part::Two,
+                solve: |input| reference::part_two(input).map(Answer::from),
+                max_size: 60,
+                max_cases: None,
+            },
+        ]
+    }
+
+    fn implementations(&self) -> &'static [Implementation] {
+        &[
+            Implementation {
+                name: "stack",
+                part: Part::One,
+                solve: |input| stack::part_one(input).map(Answer::from),
+            },
+            Implementation {
+                name: "stack",
+                part: Part::Two,
+                solve: |input| stack::part_two(input).map(Answer::from),
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn part_one_works() {
-        assert_eq!(part_one(INPUT), 21)
+        assert_eq!(part_one(EXAMPLE), Ok(21))
     }
 
     #[test]
     fn part_two_works() {
-        assert_eq!(part_two(INPUT), 8)
+        assert_eq!(part_two(EXAMPLE), Ok(8))
+    }
+
+    #[test]
+    fn forests_longer_than_they_are_wide() {
+        let input = "703\n090\n163\n177\n522";
+        assert_eq!(part_one(input), Ok(15));
+        assert_eq!(part_two(input), Ok(3));
+    }
+
+    #[test]
+    fn forests_without_an_interior() {
+        for (input, visible) in [("12\n34", 4), ("5", 1), ("123", 3), ("1\n2\n3", 3)] {
+            assert_eq!(part_one(input), Ok(visible), "{:?}", input);
+            assert_eq!(part_one(input), reference::part_one(input), "{:?}", input);
+            assert_eq!(part_two(input), Ok(0), "{:?}", input);
+            assert_eq!(part_two(input), reference::part_two(input), "{:?}", input);
+        }
+
+        let error = parse("").unwrap_err();
+        assert_eq!(error.message, "expected a forest of tree heights");
+        let error = parse("12\n  \n34").unwrap_err();
+        assert_eq!(
+            (error.line, error.message.as_str()),
+            (2, "expected a row of tree heights")
+        );
+    }
+
+    #[test]
+    fn stack_works() {
+        assert_eq!(stack::part_one(EXAMPLE), Ok(21));
+        assert_eq!(stack::part_two(EXAMPLE), Ok(8));
+        assert_eq!(stack::part_one("703\n090\n163\n177\n522"), Ok(15));
+        assert_eq!(stack::part_two("703\n090\n163\n177\n522"), Ok(3));
+    }
+
+    #[test]
+    fn linting_row_widths() {
+        let problems = lint("303\n25\n303\n2555");
+        let lines: Vec<usize> = problems.iter().map(|problem| problem.line).collect();
+        assert_eq!(lines, vec![2, 4]);
+        assert_eq!(
+            problems[1].message,
+            "expected a row of 3 trees like the first, found 4"
+        );
+        assert!(lint(EXAMPLE).is_empty());
     }
 
     #[test]
     fn forest_from_str() {
-        let forest = Forest::from(INPUT);
+        let forest = Forest::try_from(EXAMPLE).unwrap();
         assert_eq!(forest.length, 5);
         assert_eq!(forest.width, 5);
         assert_eq!(forest.rows[0], vec![3, 0, 3, 7, 3]);
@@ -173,7 +549,16 @@ mod tests {
 
     #[test]
     fn perimiter_count() {
-        let forest = Forest::from(INPUT);
+        let forest = Forest::try_from(EXAMPLE).unwrap();
         assert_eq!(forest.get_perimiter_tree_count(), 16);
     }
+
+    #[test]
+    fn forest_parse_errors() {
+        let error = Forest::try_from("303\n2a5").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 2, "a"));
+
+        let error = Forest::try_from("303\n25").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
 }
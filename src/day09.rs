@@ -1,9 +1,17 @@
 use std::collections::{HashSet, VecDeque};
 
-type Coordinate = (i32, i32);
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::input::lines;
+use crate::random::Rng;
+use crate::solution::{Example, Solution};
+
+const DAY: u8 = 9;
+
+pub type Coordinate = (i32, i32);
 
 #[derive(PartialEq, Debug)]
-enum MoveDirection {
+pub enum MoveDirection {
     Up,
     Down,
     Left,
@@ -11,13 +19,13 @@ enum MoveDirection {
 }
 
 #[derive(PartialEq, Debug)]
-struct Instruction {
-    direction: MoveDirection,
-    count: usize,
+pub struct Instruction {
+    pub direction: MoveDirection,
+    pub count: usize,
 }
 
 #[derive(Debug)]
-struct RopePosition {
+pub struct RopePosition {
     knot_positions: VecDeque<Coordinate>,
 }
 
@@ -30,6 +38,10 @@ impl RopePosition {
         RopePosition { knot_positions }
     }
 
+    pub fn knot_positions(&self) -> &VecDeque<Coordinate> {
+        &self.knot_positions
+    }
+
     pub fn move_rope_head(&mut self, direction: &MoveDirection) {
         let mut new_head = self.knot_positions.pop_front().unwrap();
         match direction {
@@ -55,74 +67,94 @@ impl RopePosition {
     }
 }
 
-impl From<&str> for Instruction {
-    fn from(line: &str) -> Self {
-        let (dir, count) = line.trim().split_once(' ').unwrap();
+impl TryFrom<&str> for Instruction {
+    type Error = ParseError;
 
-        let parsed_count: usize = count.parse().unwrap();
-        match dir {
-            "U" => Instruction {
-                direction: MoveDirection::Up,
-                count: parsed_count,
-            },
-            "D" => Instruction {
-                direction: MoveDirection::Down,
-                count: parsed_count,
-            },
-            "L" => Instruction {
-                direction: MoveDirection::Left,
-                count: parsed_count,
-            },
-            "R" => Instruction {
-                direction: MoveDirection::Right,
-                count: parsed_count,
-            },
-            _ => panic!("invalid direction provided"),
-        }
+    fn try_from(line: &str) -> Result<Self, Self::Error> {
+        let (dir, count) = match line.trim().split_once(' ') {
+            Some(split) => split,
+            None => {
+                return Err(ParseError::missing(
+                    DAY,
+                    line,
+                    "expected a direction and step count",
+                ))
+            }
+        };
+
+        let parsed_count: usize = count
+            .parse()
+            .map_err(|_| ParseError::new(DAY, line, count, "expected a step count"))?;
+        let direction = match dir {
+            "U" => MoveDirection::Up,
+            "D" => MoveDirection::Down,
+            "L" => MoveDirection::Left,
+            "R" => MoveDirection::Right,
+            _ => return Err(ParseError::new(DAY, line, dir, "expected U, D, L or R")),
+        };
+
+        Ok(Instruction {
+            direction,
+            count: parsed_count,
+        })
     }
 }
 
-pub fn part_one(input: &str) -> usize {
+pub fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    lines(input)
+        .enumerate()
+        .map(|(index, line)| Instruction::try_from(line).map_err(|error| error.on_line(index + 1)))
+        .collect()
+}
+
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
     let mut positions = RopePosition::new(1);
 
     let mut tail_positions: HashSet<Coordinate> = HashSet::new();
 
     tail_positions.insert(*positions.knot_positions.back().unwrap());
 
-    for line in input.lines() {
-        let instruction = Instruction::from(line);
+    for instruction in parse(input)? {
         for _ in 0..instruction.count {
             positions.move_rope_head(&instruction.direction);
             tail_positions.insert(*positions.knot_positions.back().unwrap());
         }
     }
 
-    tail_positions.len()
+    Ok(tail_positions.len())
 }
 
-pub fn part_two(input: &str) -> usize {
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
     let mut positions = RopePosition::new(9);
 
     let mut tail_positions: HashSet<Coordinate> = HashSet::new();
 
     tail_positions.insert(*positions.knot_positions.back().unwrap());
 
-    for line in input.lines() {
-        let instruction = Instruction::from(line);
+    for instruction in parse(input)? {
         for _ in 0..instruction.count {
             positions.move_rope_head(&instruction.direction);
             tail_positions.insert(*positions.knot_positions.back().unwrap());
         }
     }
 
-    tail_positions.len()
+    Ok(tail_positions.len())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+// `size` moves of one to twenty steps.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            format!(
+                "{} {}\n",
+                rng.choose(&['U', 'D', 'L', 'R']),
+                rng.range(1..=20)
+            )
+        })
+        .collect()
+}
 
-    const INPUT: &str = "R 4
+pub const EXAMPLE: &str = "R 4
 U 4
 L 3
 D 1
@@ -131,45 +163,97 @@ D 1
 L 5
 R 2";
 
+pub struct Day09;
+
+impl Solution for Day09 {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Rope Bridge"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_| ())
+    }
+
+    fn part_one(&self, input: &str) -> Result<Answer, ParseError> {
+        part_one(input).map(Answer::from)
+    }
+
+    fn part_two(&self, input: &str) -> Result<Answer, ParseError> {
+        part_two(input).map(Answer::from)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: EXAMPLE,
+            part_one: Some("13"),
+            part_two: Some("1"),
+        }]
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn part_one_works() {
-        assert_eq!(part_one(INPUT), 13)
+        assert_eq!(part_one(EXAMPLE), Ok(13))
     }
 
     #[test]
     fn part_two_works() {
-        assert_eq!(part_two(INPUT), 1)
+        assert_eq!(part_two(EXAMPLE), Ok(1))
     }
 
     #[test]
     fn parsing_instruction() {
         assert_eq!(
-            Instruction::from("R 12"),
-            Instruction {
+            Instruction::try_from("R 12"),
+            Ok(Instruction {
                 direction: MoveDirection::Right,
                 count: 12
-            }
+            })
         );
         assert_eq!(
-            Instruction::from("L 4"),
-            Instruction {
+            Instruction::try_from("L 4"),
+            Ok(Instruction {
                 direction: MoveDirection::Left,
                 count: 4
-            }
+            })
         );
         assert_eq!(
-            Instruction::from("U 9"),
-            Instruction {
+            Instruction::try_from("U 9"),
+            Ok(Instruction {
                 direction: MoveDirection::Up,
                 count: 9
-            }
+            })
         );
         assert_eq!(
-            Instruction::from("D 2"),
-            Instruction {
+            Instruction::try_from("D 2"),
+            Ok(Instruction {
                 direction: MoveDirection::Down,
                 count: 2
-            }
+            })
+        );
+    }
+
+    #[test]
+    fn parsing_instruction_errors() {
+        let error = Instruction::try_from("X 3").unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (1, "X"));
+
+        let error = parse("R 4\nU four").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (2, 3, "four")
         );
     }
 }
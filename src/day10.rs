@@ -1,24 +1,31 @@
-use std::fmt::{Display, Formatter, Result};
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::input::lines;
+use crate::ocr::{read_letters, OcrError};
+use crate::random::Rng;
+use crate::solution::{Example, Solution};
+
+const DAY: u8 = 10;
 
 #[derive(Debug, PartialEq)]
-enum Operation {
+pub enum Operation {
     Noop,
     AddX(i32),
 }
 
 const CLOCK_CYCLE_TO_MEASURE: [usize; 6] = [20, 60, 100, 140, 180, 220];
 
-trait ProcessorObserver {
+pub trait ProcessorObserver {
     fn update(&mut self, clock_cycle: usize, register_value: i32);
 }
 
-struct Processor<'a, T: ProcessorObserver> {
+pub struct Processor<'a, T: ProcessorObserver> {
     cycle_count: usize,
     register_value: i32,
     observer: &'a mut T,
 }
 
-struct SignalStrengthStore(i32);
+pub struct SignalStrengthStore(pub i32);
 
 pub struct Screen([char; 240]);
 
@@ -32,8 +39,9 @@ impl ProcessorObserver for SignalStrengthStore {
 
 impl ProcessorObserver for Screen {
     fn update(&mut self, clock_cycle: usize, register_value: i32) {
-        let horizontal_pixel_count: i32 = (clock_cycle % 40).try_into().unwrap();
-        if (register_value - 1..=register_value + 1).contains(&(horizontal_pixel_count - 1)) {
+        // The pixel drawn during cycle N is pixel N - 1, so the last column is 39, not -1.
+        let horizontal_pixel: i32 = ((clock_cycle - 1) % 40).try_into().unwrap();
+        if (register_value - 1..=register_value + 1).contains(&horizontal_pixel) {
             if let Some(pixel) = self.0.get_mut(clock_cycle - 1) {
                 *pixel = '#'
             };
@@ -42,20 +50,35 @@ impl ProcessorObserver for Screen {
 }
 
 impl Screen {
-    fn new() -> Self {
+    pub fn new() -> Self {
         Screen(['.'; 240])
     }
+
+    pub fn pixels(&self) -> &[char; 240] {
+        &self.0
+    }
+
+    pub fn rows(&self) -> Vec<Vec<bool>> {
+        self.0
+            .chunks(40)
+            .map(|row| row.iter().map(|pixel| *pixel == '#').collect())
+            .collect()
+    }
+
+    pub fn letters(&self) -> Result<String, OcrError> {
+        read_letters(&self.rows())
+    }
 }
 
-impl Display for Screen {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        writeln!(f)?;
-        writeln!(f, "        {}", self.0[0..40].iter().collect::<String>())?;
-        writeln!(f, "        {}", self.0[40..80].iter().collect::<String>())?;
-        writeln!(f, "        {}", self.0[80..120].iter().collect::<String>())?;
-        writeln!(f, "        {}", self.0[120..160].iter().collect::<String>())?;
-        writeln!(f, "        {}", self.0[160..200].iter().collect::<String>())?;
-        writeln!(f, "        {}", self.0[200..240].iter().collect::<String>())
+impl Default for Screen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Screen> for Answer {
+    fn from(screen: Screen) -> Self {
+        Answer::Grid(screen.rows())
     }
 }
 
@@ -89,107 +112,76 @@ impl<'a, T: ProcessorObserver> Processor<'a, T> {
     }
 }
 
-impl From<&str> for Operation {
-    fn from(input: &str) -> Self {
-        if input == "noop" {
-            Operation::Noop
+impl TryFrom<&str> for Operation {
+    type Error = ParseError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        let instruction = input.trim();
+        if instruction == "noop" {
+            Ok(Operation::Noop)
+        } else if let Some(add_val) = instruction.strip_prefix("addx ") {
+            let add_val = add_val.trim();
+            add_val
+                .parse::<i32>()
+                .map(Operation::AddX)
+                .map_err(|_| ParseError::new(DAY, input, add_val, "expected a number to add"))
         } else {
-            let (_, add_val) = input.split_at(4);
-            Operation::AddX(add_val.trim().parse::<i32>().unwrap())
+            Err(ParseError::new(
+                DAY,
+                input,
+                instruction,
+                "expected 'noop' or 'addx <value>'",
+            ))
         }
     }
 }
 
-pub fn part_one(input: &str) -> i32 {
+pub fn parse(input: &str) -> Result<Vec<Operation>, ParseError> {
+    lines(input)
+        .enumerate()
+        .map(|(index, line)| Operation::try_from(line).map_err(|error| error.on_line(index + 1)))
+        .collect()
+}
+
+pub fn part_one(input: &str) -> Result<i32, ParseError> {
     let mut signal = SignalStrengthStore(0);
     let mut processor = Processor::new(&mut signal);
 
-    input
-        .lines()
-        .map(Operation::from)
+    parse(input)?
+        .into_iter()
         .for_each(|op| processor.execute_operation(op));
 
-    signal.0
+    Ok(signal.0)
 }
 
-pub fn part_two(input: &str) -> Screen {
+pub fn part_two(input: &str) -> Result<Screen, ParseError> {
     let mut screen = Screen::new();
     let mut processor = Processor::new(&mut screen);
 
-    input
-        .lines()
-        .map(Operation::from)
+    parse(input)?
+        .into_iter()
         .for_each(|op| processor.execute_operation(op));
 
-    screen
+    Ok(screen)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn part_one_works() {
-        assert_eq!(part_one(INPUT), 13140)
-    }
-
-    #[test]
-    fn part_two_works() {
-        assert_eq!(
-            part_two(INPUT).0.iter().collect::<String>(),
-            EXPECTED_SCREEN_CHARS
-        );
-    }
-
-    #[test]
-    fn parsing_operation() {
-        assert_eq!(Operation::from("noop"), Operation::Noop);
-        assert_eq!(Operation::from("addx 8"), Operation::AddX(8));
-        assert_eq!(Operation::from("addx -99"), Operation::AddX(-99));
-    }
-
-    #[test]
-    fn observability_with_noop_works() {
-        let mut signal = SignalStrengthStore(1);
-        let mut processor = Processor {
-            cycle_count: 18,
-            register_value: 0,
-            observer: &mut signal,
-        };
-
-        processor.execute_operation(Operation::Noop);
-
-        assert_eq!(processor.cycle_count, 19);
-        assert_eq!(signal.0, 1);
-    }
-
-    #[test]
-    fn observability_with_add_works() {
-        let mut signal = SignalStrengthStore(0);
-        let mut processor = Processor {
-            cycle_count: 19,
-            register_value: 1,
-            observer: &mut signal,
-        };
-
-        processor.execute_operation(Operation::AddX(9));
-
-        assert_eq!(processor.cycle_count, 21);
-        assert_eq!(processor.register_value, 10);
-        assert_eq!(signal.0, 20);
-    }
-
-    #[test]
-    fn observability_with_screen_works() {
-        let mut screen = Screen::new();
-        let mut processor = Processor::new(&mut screen);
-
-        processor.execute_operation(Operation::Noop);
-
-        assert_eq!(screen.0[0], '#')
-    }
+// `size` instructions that keep the register between -1 and 40, so the sprite stays on the screen.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut register: i64 = 1;
+    (0..size)
+        .map(|_| {
+            if rng.chance(1, 3) {
+                String::from("noop\n")
+            } else {
+                let value = rng.range(-1 - register..=40 - register);
+                register += value;
+                format!("addx {}\n", value)
+            }
+        })
+        .collect()
+}
 
-    const INPUT: &str = "addx 15
+pub const EXAMPLE: &str = "addx 15
 addx -11
 addx 6
 addx -3
@@ -336,5 +328,165 @@ noop
 noop
 noop";
 
-    const EXPECTED_SCREEN_CHARS: &str = "##..##..##..##..##..##..##..##..##..##..###...###...###...###...###...###...###.####....####....####....####....####....#####.....#####.....#####.....#####.....######......######......######......###.#######.......#######.......#######.....";
+// A screen that spells letters is read so the answer can be pasted and verified, and one that
+// isn't letter-shaped at all, like the example's stripes, is shown as it is. A letter missing
+// from the font fails the part rather than hiding among the pixels.
+fn read_screen(input: &str, screen: Screen) -> Result<Answer, ParseError> {
+    match screen.letters() {
+        Ok(letters) => Ok(Answer::Text(letters)),
+        Err(OcrError::UnknownGlyph { index, glyph }) => {
+            let last_line = lines(input).last().unwrap_or(input);
+            let message = format!("expected letter {} on the screen to be in the font", index);
+            Err(ParseError::new(DAY, last_line, &glyph, message)
+                .on_line(lines(input).count().max(1)))
+        }
+        Err(_) => Ok(Answer::from(screen)),
+    }
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Cathode-Ray Tube"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_| ())
+    }
+
+    fn part_one(&self, input: &str) -> Result<Answer, ParseError> {
+        part_one(input).map(Answer::from)
+    }
+
+    fn part_two(&self, input: &str) -> Result<Answer, ParseError> {
+        read_screen(input, part_two(input)?)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: EXAMPLE,
+            part_one: Some("13140"),
+            part_two: Some(
+                "##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....",
+            ),
+        }]
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_one_works() {
+        assert_eq!(part_one(EXAMPLE), Ok(13140))
+    }
+
+    #[test]
+    fn part_two_works() {
+        assert_eq!(
+            part_two(EXAMPLE).unwrap().0.iter().collect::<String>(),
+            EXPECTED_SCREEN_CHARS
+        );
+    }
+
+    #[test]
+    fn example_screen_is_not_letters() {
+        let screen = part_two(EXAMPLE).unwrap();
+        assert_eq!(screen.letters(), Err(OcrError::LitSpacer { column: 5 }));
+        assert_eq!(Day10.part_two(EXAMPLE), Ok(Answer::Grid(screen.rows())));
+    }
+
+    #[test]
+    fn letters_missing_from_the_font() {
+        let mut screen = Screen::new();
+        for (index, pixel) in screen.0.iter_mut().enumerate() {
+            if index % 5 != 4 {
+                *pixel = '#';
+            }
+        }
+        let error = read_screen(EXAMPLE, screen).unwrap_err();
+        assert_eq!(error.line, 146);
+        assert_eq!(
+            error.message,
+            "expected letter 1 on the screen to be in the font"
+        );
+        assert_eq!(error.text, ["####"; 6].join("\n"));
+    }
+
+    #[test]
+    fn parsing_operation() {
+        assert_eq!(Operation::try_from("noop"), Ok(Operation::Noop));
+        assert_eq!(Operation::try_from("addx 8"), Ok(Operation::AddX(8)));
+        assert_eq!(Operation::try_from("addx -99"), Ok(Operation::AddX(-99)));
+    }
+
+    #[test]
+    fn parsing_operation_errors() {
+        let error = Operation::try_from("addx eight").unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (6, "eight"));
+
+        let error = parse("noop\nmulx 3").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (2, 1, "mulx 3")
+        );
+    }
+
+    #[test]
+    fn observability_with_noop_works() {
+        let mut signal = SignalStrengthStore(1);
+        let mut processor = Processor {
+            cycle_count: 18,
+            register_value: 0,
+            observer: &mut signal,
+        };
+
+        processor.execute_operation(Operation::Noop);
+
+        assert_eq!(processor.cycle_count, 19);
+        assert_eq!(signal.0, 1);
+    }
+
+    #[test]
+    fn observability_with_add_works() {
+        let mut signal = SignalStrengthStore(0);
+        let mut processor = Processor {
+            cycle_count: 19,
+            register_value: 1,
+            observer: &mut signal,
+        };
+
+        processor.execute_operation(Operation::AddX(9));
+
+        assert_eq!(processor.cycle_count, 21);
+        assert_eq!(processor.register_value, 10);
+        assert_eq!(signal.0, 20);
+    }
+
+    #[test]
+    fn observability_with_screen_works() {
+        let mut screen = Screen::new();
+        let mut processor = Processor::new(&mut screen);
+
+        processor.execute_operation(Operation::Noop);
+
+        assert_eq!(screen.0[0], '#')
+    }
+
+    const EXPECTED_SCREEN_CHARS: &str = "##..##..##..##..##..##..##..##..##..##..###...###...###...###...###...###...###.####....####....####....####....####....#####.....#####.....#####.....#####.....######......######......######......###########.......#######.......#######.....";
 }
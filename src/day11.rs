@@ -1,85 +1,151 @@
 use std::collections::VecDeque;
 
+use crate::answer::Answer;
+use crate::error::{lines_before, ParseError};
+use crate::input::blocks;
+use crate::lint::Problem;
+use crate::random::Rng;
+use crate::solution::{Example, Part, Reference, Solution};
+
+const DAY: u8 = 11;
+
 #[derive(Debug, PartialEq, Clone)]
-enum Operand {
+pub enum Operand {
     Plus(u64),
     Multiply(u64),
     Pow(u32),
 }
 
 #[derive(Debug, PartialEq, Clone)]
-struct Monkey {
-    items: VecDeque<u64>,
-    operation: Operand,
-    divisible_by_check: u64,
-    connected_monkey_indexes: (usize, usize),
-    inspection_count: u64,
+pub struct Monkey {
+    pub items: VecDeque<u64>,
+    pub operation: Operand,
+    pub divisible_by_check: u64,
+    pub connected_monkey_indexes: (usize, usize),
+    pub inspection_count: u64,
 }
 
-struct PassToMonkeyInstruction(u64, usize);
-
-impl From<&str> for Monkey {
-    fn from(value: &str) -> Self {
-        let mut iter = value.lines().skip(1);
-        let starting_items: VecDeque<u64> = iter
-            .next()
-            .unwrap()
-            .trim()
-            .split_at(16)
-            .1
-            .split(", ")
-            .map(|val| val.parse::<u64>().unwrap())
-            .collect();
+pub struct PassToMonkeyInstruction(pub u64, pub usize);
 
-        let (op_str, val_str) = iter.next().unwrap().trim().split_at(21).1.split_at(1);
+// Where a monkey's "throw to monkey N" names its target, kept so `parse` can point at a target
+// that doesn't exist once it knows how many monkeys there are.
+struct ThrowTarget<'a> {
+    line_number: usize,
+    line: &'a str,
+    text: &'a str,
+    monkey: usize,
+}
 
-        let op_value = match val_str.trim() {
-            "old" => u64::MAX,
-            val => val.parse::<u64>().unwrap(),
-        };
+// Returns the line number, the line and whatever follows `prefix` on it. Line numbers are
+// relative to the start of the monkey's block.
+fn strip_line_prefix<'a>(
+    block: &str,
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+    prefix: &str,
+) -> Result<(usize, &'a str, &'a str), ParseError> {
+    match lines.next() {
+        Some((index, line)) => match line.trim().strip_prefix(prefix) {
+            Some(rest) => Ok((index + 1, line, rest)),
+            None => Err(
+                ParseError::new(DAY, line, line.trim(), format!("expected '{}'", prefix))
+                    .on_line(index + 1),
+            ),
+        },
+        None => {
+            let last_line = block.lines().last().unwrap_or(block);
+            Err(
+                ParseError::missing(DAY, last_line, format!("expected '{}'", prefix))
+                    .on_line(block.lines().count().max(1)),
+            )
+        }
+    }
+}
 
-        let operation = match op_str {
-            "+" => {
-                if op_value == u64::MAX {
-                    Operand::Multiply(2)
-                } else {
-                    Operand::Plus(op_value)
-                }
-            }
-            "*" => {
-                if op_value == u64::MAX {
-                    Operand::Pow(2)
-                } else {
-                    Operand::Multiply(op_value)
-                }
-            }
-            _ => panic!("Cannot parse operand"),
-        };
+fn parse_number<T: std::str::FromStr>(
+    line_number: usize,
+    line: &str,
+    value: &str,
+) -> Result<T, ParseError> {
+    value.trim().parse::<T>().map_err(|_| {
+        ParseError::new(DAY, line, value.trim(), "expected a number").on_line(line_number)
+    })
+}
 
-        let divisible_by_check = iter
-            .next()
-            .unwrap()
-            .trim()
-            .split_at(19)
-            .1
-            .parse::<u64>()
-            .unwrap();
-
-        let connected_monkeys: Vec<usize> = iter
-            .map(|line| match line.split_once("throw to monkey") {
-                Some((_, index)) => index.trim().parse::<usize>().unwrap(),
-                None => panic!(),
-            })
-            .collect();
+impl TryFrom<&str> for Monkey {
+    type Error = ParseError;
 
-        Monkey {
-            items: starting_items,
-            operation,
-            divisible_by_check,
-            connected_monkey_indexes: (connected_monkeys[0], connected_monkeys[1]),
-            inspection_count: 0,
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        parse_monkey(value).map(|(monkey, _)| monkey)
+    }
+}
+
+fn parse_monkey(value: &str) -> Result<(Monkey, [ThrowTarget<'_>; 2]), ParseError> {
+    let mut iter = value.lines().enumerate();
+    strip_line_prefix(value, &mut iter, "Monkey ")?;
+
+    let (items_line, line, items) = strip_line_prefix(value, &mut iter, "Starting items: ")?;
+    let starting_items: VecDeque<u64> = items
+        .split(", ")
+        .map(|val| parse_number(items_line, line, val))
+        .collect::<Result<VecDeque<u64>, ParseError>>()?;
+
+    let (operation_line, line, operation) =
+        strip_line_prefix(value, &mut iter, "Operation: new = old ")?;
+    let (op_str, val_str) = operation.split_at(operation.chars().next().map_or(0, char::len_utf8));
+
+    let op_value = match val_str.trim() {
+        "old" => None,
+        val => Some(parse_number::<u64>(operation_line, line, val)?),
+    };
+
+    let operation = match (op_str, op_value) {
+        ("+", None) => Operand::Multiply(2),
+        ("+", Some(op_value)) => Operand::Plus(op_value),
+        ("*", None) => Operand::Pow(2),
+        ("*", Some(op_value)) => Operand::Multiply(op_value),
+        _ => {
+            return Err(
+                ParseError::new(DAY, line, op_str, "expected '+' or '*'").on_line(operation_line)
+            )
         }
+    };
+
+    let (test_line, line, divisor) = strip_line_prefix(value, &mut iter, "Test: divisible by ")?;
+    let divisible_by_check = parse_number::<u64>(test_line, line, divisor)?;
+    if divisible_by_check == 0 {
+        return Err(
+            ParseError::new(DAY, line, divisor.trim(), "expected a non-zero divisor")
+                .on_line(test_line),
+        );
+    }
+
+    let mut throw_target = |prefix: &str| -> Result<ThrowTarget, ParseError> {
+        let (line_number, line, text) = strip_line_prefix(value, &mut iter, prefix)?;
+        Ok(ThrowTarget {
+            line_number,
+            line,
+            text: text.trim(),
+            monkey: parse_number::<usize>(line_number, line, text)?,
+        })
+    };
+    let if_true = throw_target("If true: throw to monkey ")?;
+    let if_false = throw_target("If false: throw to monkey ")?;
+
+    if let Some((index, line)) = iter.find(|(_, line)| !line.trim().is_empty()) {
+        return Err(
+            ParseError::new(DAY, line, line.trim(), "expected the end of the monkey")
+                .on_line(index + 1),
+        );
     }
+
+    let monkey = Monkey {
+        items: starting_items,
+        operation,
+        divisible_by_check,
+        connected_monkey_indexes: (if_true.monkey, if_false.monkey),
+        inspection_count: 0,
+    };
+    Ok((monkey, [if_true, if_false]))
 }
 
 impl Monkey {
@@ -88,7 +154,7 @@ impl Monkey {
             let new_item_value = self.get_bordom_value(current_item, 3);
             self.inspection_count += 1;
 
-            if new_item_value % self.divisible_by_check == 0 {
+            if new_item_value.is_multiple_of(self.divisible_by_check) {
                 Some(PassToMonkeyInstruction(
                     new_item_value,
                     self.connected_monkey_indexes.0,
@@ -112,7 +178,7 @@ impl Monkey {
             let new_item_value = self.get_bordom_value(current_item, 1) % lowest_common_modulo;
             self.inspection_count += 1;
 
-            if new_item_value % self.divisible_by_check == 0 {
+            if new_item_value.is_multiple_of(self.divisible_by_check) {
                 Some(PassToMonkeyInstruction(
                     new_item_value,
                     self.connected_monkey_indexes.0,
@@ -138,8 +204,35 @@ impl Monkey {
     }
 }
 
-pub fn part_one(input: &str) -> u64 {
-    let mut monkeys: Vec<Monkey> = input.split("\n\n").map(Monkey::from).collect();
+pub fn parse(input: &str) -> Result<Vec<Monkey>, ParseError> {
+    let mut monkeys: Vec<Monkey> = Vec::new();
+    let mut targets: Vec<(usize, ThrowTarget)> = Vec::new();
+    for block in blocks(input) {
+        let offset = lines_before(input, block);
+        let (monkey, throws) = parse_monkey(block).map_err(|error| error.offset_lines(offset))?;
+        monkeys.push(monkey);
+        targets.extend(throws.map(|target| (offset, target)));
+    }
+
+    if monkeys.is_empty() {
+        return Err(ParseError::missing(DAY, input, "expected a monkey"));
+    }
+    for (offset, target) in targets {
+        if target.monkey >= monkeys.len() {
+            return Err(ParseError::new(
+                DAY,
+                target.line,
+                target.text,
+                format!("expected a monkey from 0 to {}", monkeys.len() - 1),
+            )
+            .on_line(offset + target.line_number));
+        }
+    }
+    Ok(monkeys)
+}
+
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
+    let mut monkeys: Vec<Monkey> = parse(input)?;
 
     for _round in 0..20 {
         for monkey_index in 0..monkeys.len() {
@@ -159,15 +252,15 @@ pub fn part_one(input: &str) -> u64 {
 
     monkeys.sort_by(|a, b| b.inspection_count.partial_cmp(&a.inspection_count).unwrap());
 
-    monkeys
+    Ok(monkeys
         .iter()
         .take(2)
         .map(|monkey| monkey.inspection_count)
-        .product()
+        .product())
 }
 
-pub fn part_two(input: &str) -> u64 {
-    let mut monkeys: Vec<Monkey> = input.split("\n\n").map(Monkey::from).collect();
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
+    let mut monkeys: Vec<Monkey> = parse(input)?;
 
     let lowest_common_modulo: u64 = monkeys
         .iter()
@@ -193,11 +286,365 @@ pub fn part_two(input: &str) -> u64 {
 
     monkeys.sort_by(|a, b| b.inspection_count.partial_cmp(&a.inspection_count).unwrap());
 
-    monkeys
+    Ok(monkeys
         .iter()
         .take(2)
         .map(|monkey| monkey.inspection_count)
-        .product()
+        .product())
+}
+
+// Part two again without the modulo, keeping every worry level exactly. Only practical while no
+// monkey squares, as in the generated inputs: the puzzle input squares, and its worry levels
+// would grow to billions of digits.
+pub mod reference {
+    use std::collections::VecDeque;
+
+    use super::{parse, Operand};
+    use crate::error::ParseError;
+
+    // An unbounded worry level in base 2^32 digits, least significant first.
+    #[derive(Debug, Clone, PartialEq)]
+    struct Worry(Vec<u32>);
+
+    impl Worry {
+        fn new(value: u64) -> Self {
+            let mut worry = Worry(Vec::new());
+            worry.add(value);
+            worry
+        }
+
+        fn add(&mut self, value: u64) {
+            let mut carry = value;
+            for digit in self.0.iter_mut() {
+                if carry == 0 {
+                    return;
+                }
+                let sum = u64::from(*digit) + (carry & u64::from(u32::MAX));
+                *digit = sum as u32;
+                carry = (carry >> 32) + (sum >> 32);
+            }
+            while carry > 0 {
+                self.0.push(carry as u32);
+                carry >>= 32;
+            }
+        }
+
+        fn mul(&mut self, value: u64) {
+            let (low, high) = (value & u64::from(u32::MAX), value >> 32);
+            let shifted = high > 0;
+            let original = if shifted { self.0.clone() } else { Vec::new() };
+
+            self.mul_digit(low);
+            if shifted {
+                let mut upper = Worry(original);
+                upper.mul_digit(high);
+                upper.0.insert(0, 0);
+                self.add_worry(&upper);
+            }
+        }
+
+        fn mul_digit(&mut self, value: u64) {
+            let mut carry = 0;
+            for digit in self.0.iter_mut() {
+                let product = u64::from(*digit) * value + carry;
+                *digit = product as u32;
+                carry = product >> 32;
+            }
+            if carry > 0 {
+                self.0.push(carry as u32);
+            }
+            while self.0.last() == Some(&0) {
+                self.0.pop();
+            }
+        }
+
+        fn add_worry(&mut self, other: &Worry) {
+            if self.0.len() < other.0.len() {
+                self.0.resize(other.0.len(), 0);
+            }
+            let mut carry = 0;
+            for (index, digit) in self.0.iter_mut().enumerate() {
+                let sum = u64::from(*digit) + u64::from(*other.0.get(index).unwrap_or(&0)) + carry;
+                *digit = sum as u32;
+                carry = sum >> 32;
+            }
+            if carry > 0 {
+                self.0.push(carry as u32);
+            }
+        }
+
+        fn square(&self) -> Worry {
+            let mut square = Worry(Vec::new());
+            for (shift, digit) in self.0.iter().enumerate() {
+                let mut row = self.clone();
+                row.mul_digit(u64::from(*digit));
+                row.0.splice(0..0, std::iter::repeat_n(0, shift));
+                square.add_worry(&row);
+            }
+            square
+        }
+
+        fn rem(&self, divisor: u64) -> u64 {
+            // Divisors that fit a digit keep every step in u64, which is far cheaper than u128.
+            if divisor <= u64::from(u32::MAX) {
+                return self
+                    .0
+                    .iter()
+                    .rev()
+                    .fold(0, |rem, digit| ((rem << 32) | u64::from(*digit)) % divisor);
+            }
+            self.0.iter().rev().fold(0, |rem, digit| {
+                (((u128::from(rem) << 32) | u128::from(*digit)) % u128::from(divisor)) as u64
+            })
+        }
+    }
+
+    pub fn part_two(input: &str) -> Result<u64, ParseError> {
+        let monkeys = parse(input)?;
+        let mut items: Vec<VecDeque<Worry>> = monkeys
+            .iter()
+            .map(|monkey| monkey.items.iter().map(|item| Worry::new(*item)).collect())
+            .collect();
+        let mut inspections = vec![0; monkeys.len()];
+
+        for _round in 0..10000 {
+            for (index, monkey) in monkeys.iter().enumerate() {
+                for _ in 0..items[index].len() {
+                    let mut item = items[index].pop_front().unwrap();
+                    match monkey.operation {
+                        Operand::Plus(amount) => item.add(amount),
+                        Operand::Multiply(amount) => item.mul(amount),
+                        Operand::Pow(exponent) => {
+                            for _ in 1..exponent {
+                                item = item.square();
+                            }
+                        }
+                    };
+                    inspections[index] += 1;
+
+                    let target = if item.rem(monkey.divisible_by_check) == 0 {
+                        monkey.connected_monkey_indexes.0
+                    } else {
+                        monkey.connected_monkey_indexes.1
+                    };
+                    items[target].push_back(item);
+                }
+            }
+        }
+
+        inspections.sort_by(|a, b| b.cmp(a));
+        Ok(inspections.iter().take(2).product())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn worry_arithmetic() {
+            let mut big = Worry::new(u64::MAX);
+            big.mul(u64::MAX);
+            assert_eq!(big.0, vec![1, 0, u32::MAX - 1, u32::MAX]);
+            assert_eq!(big.rem(10), 5);
+            assert_eq!(big.rem(u64::MAX), 0);
+            assert_eq!(Worry::new(u64::MAX).square(), big);
+
+            big.add(u64::MAX);
+            assert_eq!(big.rem(1 << 32), 0);
+            assert_eq!(Worry::new(0), Worry(Vec::new()));
+        }
+    }
+}
+
+// Monkeys must be numbered in order, since throws go by position, and every throw must go to
+// another monkey that exists.
+pub fn lint(input: &str) -> Vec<Problem> {
+    let mut problems: Vec<Problem> = Vec::new();
+    let monkeys: Vec<&str> = blocks(input).collect();
+
+    // Lines the parser rejects are left for it to report.
+    for (index, block) in monkeys.iter().enumerate() {
+        let offset = lines_before(input, block);
+        for (line_index, line) in block.lines().enumerate() {
+            let line_number = offset + line_index + 1;
+            let line = line.trim();
+
+            if let Some(number) = line
+                .strip_prefix("Monkey ")
+                .and_then(|rest| rest.strip_suffix(':'))
+                .and_then(|number| number.parse::<usize>().ok())
+            {
+                if number != index {
+                    problems.push(Problem::new(
+                        DAY,
+                        line_number,
+                        format!("expected monkey {}, found monkey {}", index, number),
+                    ));
+                }
+            }
+
+            let target = ["If true: throw to monkey ", "If false: throw to monkey "]
+                .iter()
+                .find_map(|prefix| line.strip_prefix(prefix))
+                .and_then(|target| target.parse::<usize>().ok());
+            match target {
+                Some(target) if target >= monkeys.len() => problems.push(Problem::new(
+                    DAY,
+                    line_number,
+                    format!(
+                        "throws to monkey {}, but the monkeys only go up to {}",
+                        target,
+                        monkeys.len() - 1
+                    ),
+                )),
+                Some(target) if target == index => problems.push(Problem::new(
+                    DAY,
+                    line_number,
+                    format!("monkey {} throws to itself", index),
+                )),
+                _ => (),
+            }
+        }
+    }
+
+    problems
+}
+
+const DIVISORS: [u64; 8] = [2, 3, 5, 7, 11, 13, 17, 19];
+
+// `size` items spread over two to eight monkeys, shaped so worry levels fit in a u64 without the
+// relief of part two's modulo: monkey 0 adds and throws to higher numbered monkeys, every other
+// monkey adds or multiplies by at most 13 and throws to lower numbered ones, so an item meets at
+// most one multiplying monkey a round. Every divisor is a different prime.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let monkeys = (rng.range(2..=8) as usize).min(size.max(2));
+    let mut items: Vec<Vec<u64>> = vec![Vec::new(); monkeys];
+    for index in 0..size.max(monkeys) {
+        let monkey = if index < monkeys {
+            index
+        } else {
+            rng.index(monkeys)
+        };
+        items[monkey].push(rng.range(50..=99) as u64);
+    }
+
+    let mut divisors = DIVISORS;
+    rng.shuffle(&mut divisors);
+
+    let descriptions: Vec<String> = items
+        .iter()
+        .enumerate()
+        .map(|(monkey, items)| {
+            let operation = if monkey > 0 && rng.chance(1, 2) {
+                format!("* {}", rng.range(2..=13))
+            } else {
+                format!("+ {}", rng.range(1..=8))
+            };
+            let target = |rng: &mut Rng| match monkey {
+                0 => rng.range(1..=monkeys as i64 - 1),
+                _ => rng.range(0..=monkey as i64 - 1),
+            };
+            let items: Vec<String> = items.iter().map(u64::to_string).collect();
+
+            format!(
+                "Monkey {}:
+  Starting items: {}
+  Operation: new = old {}
+  Test: divisible by {}
+    If true: throw to monkey {}
+    If false: throw to monkey {}",
+                monkey,
+                items.join(", "),
+                operation,
+                divisors[monkey],
+                target(rng),
+                target(rng)
+            )
+        })
+        .collect();
+
+    descriptions.join("\n\n") + "\n"
+}
+
+pub const EXAMPLE: &str = "Monkey 0:
+Starting items: 79, 98
+Operation: new = old * 19
+Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+Starting items: 54, 65, 75, 74
+Operation: new = old + 6
+Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+Starting items: 79, 60, 97
+Operation: new = old * old
+Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+Starting items: 74
+Operation: new = old + 3
+Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1";
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "Monkey in the Middle"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_| ())
+    }
+
+    fn part_one(&self, input: &str) -> Result<Answer, ParseError> {
+        part_one(input).map(Answer::from)
+    }
+
+    fn part_two(&self, input: &str) -> Result<Answer, ParseError> {
+        part_two(input).map(Answer::from)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: EXAMPLE,
+            part_one: Some("10605"),
+            part_two: Some("2713310158"),
+        }]
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn lint(&self, input: &str) -> Vec<Problem> {
+        lint(input)
+    }
+
+    // The reference keeps every worry level whole through 10000 rounds, so each multiplication
+    // adds digits for good: an input of 4 items already takes up to 0.8s in a debug build and 8
+    // items twice that. A few small inputs still exercise every operation and throw, which is
+    // what the modulus trick in the real solver has to get right.
+    fn references(&self) -> &'static [Reference] {
+        &[Reference {
+            part: Part::Two,
+            solve: |input| reference::part_two(input).map(Answer::from),
+            max_size: 4,
+            max_cases: Some(4),
+        }]
+    }
 }
 
 #[cfg(test)]
@@ -206,12 +653,12 @@ mod tests {
 
     #[test]
     fn part_one_works() {
-        assert_eq!(part_one(INPUT), 10605)
+        assert_eq!(part_one(EXAMPLE), Ok(10605))
     }
 
     #[test]
     fn part_two_works() {
-        assert_eq!(part_two(INPUT), 2713310158)
+        assert_eq!(part_two(EXAMPLE), Ok(2713310158))
     }
 
     #[test]
@@ -246,35 +693,72 @@ Test: divisible by 13
             inspection_count: 0,
         };
 
-        assert_eq!(Monkey::from(INPUT_1), expected_monkey_1);
-        assert_eq!(Monkey::from(INPUT_2), expected_monkey_2);
+        assert_eq!(Monkey::try_from(INPUT_1), Ok(expected_monkey_1));
+        assert_eq!(Monkey::try_from(INPUT_2), Ok(expected_monkey_2));
     }
 
-    const INPUT: &str = "Monkey 0:
-Starting items: 79, 98
-Operation: new = old * 19
-Test: divisible by 23
-    If true: throw to monkey 2
-    If false: throw to monkey 3
+    #[test]
+    fn monkey_parsing_errors() {
+        let error = parse(&EXAMPLE.replace("old + 6", "old - 6")).unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (10, 22, "-")
+        );
+
+        let error = parse(&EXAMPLE.replace("79, 60, 97", "79, sixty, 97")).unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (16, 21, "sixty")
+        );
+
+        let error = Monkey::try_from("Monkey 0:\nStarting items: 79, 98").unwrap_err();
+        assert_eq!((error.line, error.text.as_str()), (2, ""));
+    }
 
-Monkey 1:
-Starting items: 54, 65, 75, 74
-Operation: new = old + 6
-Test: divisible by 19
-    If true: throw to monkey 2
-    If false: throw to monkey 0
+    #[test]
+    fn linting_throws() {
+        let input = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 0
+    If false: throw to monkey 1
 
 Monkey 2:
-Starting items: 79, 60, 97
-Operation: new = old * old
-Test: divisible by 13
-    If true: throw to monkey 1
-    If false: throw to monkey 3
+  Starting items: 54
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 5
+    If false: throw to monkey 0";
+        let problems: Vec<(usize, String)> = lint(input)
+            .into_iter()
+            .map(|problem| (problem.line, problem.message))
+            .collect();
+        assert_eq!(
+            problems,
+            vec![
+                (5, String::from("monkey 0 throws to itself")),
+                (8, String::from("expected monkey 1, found monkey 2")),
+                (
+                    12,
+                    String::from("throws to monkey 5, but the monkeys only go up to 1")
+                ),
+            ]
+        );
+        assert!(lint(EXAMPLE).is_empty());
+    }
 
-Monkey 3:
-Starting items: 74
-Operation: new = old + 3
-Test: divisible by 17
-    If true: throw to monkey 0
-    If false: throw to monkey 1";
+    #[test]
+    fn throws_to_missing_monkeys_are_rejected() {
+        let input = EXAMPLE.replacen("throw to monkey 3", "throw to monkey 4", 1);
+        let error = parse(&input).unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (6, 31, "4")
+        );
+        assert_eq!(error.message, "expected a monkey from 0 to 3");
+        assert_eq!(part_one(&input), Err(error));
+
+        assert_eq!(parse("").unwrap_err().message, "expected a monkey");
+    }
 }
@@ -0,0 +1,232 @@
+use std::env;
+use std::fmt::{self, Display, Formatter};
+
+use crate::answer::Answer;
+use crate::error::SolveError;
+use crate::input::{blocks, lines};
+use crate::random::Rng;
+use crate::runner::{catch_panics, solve_caught};
+use crate::solution::{Part, Reference, Solution};
+
+// How many generated inputs each reference is checked on, for longer runs in CI.
+pub const CASES_VAR: &str = "AOC_DIFFERENTIAL_CASES";
+const DEFAULT_CASES: usize = 20;
+
+pub fn cases() -> usize {
+    env::var(CASES_VAR)
+        .ok()
+        .and_then(|cases| cases.parse::<usize>().ok())
+        .unwrap_or(DEFAULT_CASES)
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Mismatch {
+    pub day: u8,
+    pub part: Part,
+    pub seed: u64,
+    pub size: usize,
+    // The smallest input found that still disagrees, not the generated one.
+    pub input: String,
+    pub expected: Result<Answer, SolveError>,
+    pub actual: Result<Answer, SolveError>,
+}
+
+fn describe(answer: &Result<Answer, SolveError>) -> String {
+    match answer {
+        Ok(answer) => answer.to_string(),
+        Err(error) => error.to_string(),
+    }
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {:02} part {} disagrees with its reference on the input generated with seed {} \
+             and size {}, shrunk to:\n{}\nreference: {}\nsolution: {}",
+            self.day,
+            self.part,
+            self.seed,
+            self.size,
+            self.input,
+            describe(&self.expected),
+            describe(&self.actual)
+        )
+    }
+}
+
+// Two failures count as agreeing: the messages of a panic in each are bound to differ.
+fn disagree(expected: &Result<Answer, SolveError>, actual: &Result<Answer, SolveError>) -> bool {
+    match (expected, actual) {
+        (Ok(expected), Ok(actual)) => expected != actual,
+        (Err(_), Err(_)) => false,
+        _ => true,
+    }
+}
+
+// Smaller versions of `input`: without one of its blocks, then without ever shorter runs of
+// lines.
+fn candidates(input: &str) -> Vec<String> {
+    let mut candidates: Vec<String> = Vec::new();
+
+    let blocks: Vec<&str> = blocks(input).collect();
+    if blocks.len() > 1 {
+        for skipped in 0..blocks.len() {
+            let mut kept = blocks.clone();
+            kept.remove(skipped);
+            candidates.push(kept.join("\n\n") + "\n");
+        }
+    }
+
+    let lines: Vec<&str> = lines(input).collect();
+    let mut run = lines.len() / 2;
+    while run > 0 {
+        for start in (0..lines.len()).step_by(run) {
+            let end = (start + run).min(lines.len());
+            let kept = [&lines[..start], &lines[end..]].concat();
+            candidates.push(kept.join("\n") + "\n");
+        }
+        run /= 2;
+    }
+
+    candidates
+}
+
+// Greedily takes the first smaller candidate that still fails until none does.
+pub fn shrink(input: String, fails: impl Fn(&str) -> bool) -> String {
+    let mut input = input;
+    'shrinking: loop {
+        for candidate in candidates(&input) {
+            if candidate.len() < input.len() && fails(&candidate) {
+                input = candidate;
+                continue 'shrinking;
+            }
+        }
+        return input;
+    }
+}
+
+// Runs the reference and the real solver on `cases` generated inputs (fewer if the reference
+// caps them), growing from size 1 to the reference's `max_size`, and shrinks the first input
+// they disagree on.
+pub fn check(
+    solution: &dyn Solution,
+    reference: &Reference,
+    cases: usize,
+) -> Result<(), Box<Mismatch>> {
+    let outcome = |input: &str| {
+        (
+            catch_panics(|| (reference.solve)(input)),
+            solve_caught(solution, reference.part, input),
+        )
+    };
+
+    let cases = reference
+        .max_cases
+        .map_or(cases, |max_cases| cases.min(max_cases));
+    for case in 0..cases {
+        let seed = case as u64;
+        let size = 1 + case * reference.max_size.saturating_sub(1) / cases.max(2).saturating_sub(1);
+        let input = solution.generate(&mut Rng::new(seed), size);
+
+        let (expected, actual) = outcome(&input);
+        if !disagree(&expected, &actual) {
+            continue;
+        }
+
+        // Candidates the day can't parse are dropped, so the shrunk input stays a puzzle input.
+        let input = shrink(input, |candidate| {
+            solution.parse(candidate).is_ok() && {
+                let (expected, actual) = outcome(candidate);
+                disagree(&expected, &actual)
+            }
+        });
+        let (expected, actual) = outcome(&input);
+        return Err(Box::new(Mismatch {
+            day: solution.day(),
+            part: reference.part,
+            seed,
+            size,
+            input,
+            expected,
+            actual,
+        }));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day01::Day01;
+    use crate::solution::SOLUTIONS;
+
+    #[test]
+    fn references_agree_with_the_solutions() {
+        for solution in SOLUTIONS {
+            for reference in solution.references() {
+                if let Err(mismatch) = check(*solution, reference, cases()) {
+                    panic!("{}", mismatch);
+                }
+            }
+        }
+    }
+
+    // Alternative implementations get the same treatment, with the day's own solver as the oracle.
+    #[test]
+    fn implementations_agree_with_the_solutions() {
+        for solution in SOLUTIONS {
+            for implementation in solution.implementations() {
+                let as_reference = Reference {
+                    part: implementation.part,
+                    solve: implementation.solve,
+                    max_size: 60,
+                    max_cases: None,
+                };
+                if let Err(mismatch) = check(*solution, &as_reference, cases()) {
+                    panic!("{} ({})", mismatch, implementation.name);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn mismatches_are_shrunk() {
+        let wrong = Reference {
+            part: Part::One,
+            solve: |_| Ok(Answer::Integer(0)),
+            max_size: 30,
+            max_cases: None,
+        };
+        let mismatch = check(&Day01, &wrong, 5).unwrap_err();
+        assert_eq!(mismatch.seed, 0);
+        assert_eq!(lines(&mismatch.input).count(), 1);
+        assert_eq!(mismatch.expected, Ok(Answer::Integer(0)));
+        assert!(mismatch.to_string().contains("seed 0 and size 1"));
+    }
+
+    #[test]
+    fn failures_on_both_sides_agree() {
+        let panic = Err(SolveError::Panic {
+            message: String::from("a"),
+            location: None,
+        });
+        assert!(!disagree(
+            &panic,
+            &Err(SolveError::Panic {
+                message: String::from("b"),
+                location: None,
+            })
+        ));
+        assert!(disagree(&panic, &Ok(Answer::Integer(1))));
+        assert!(disagree(&Ok(Answer::Integer(2)), &Ok(Answer::Integer(1))));
+    }
+
+    #[test]
+    fn shrinking_keeps_what_fails() {
+        let input = "1\n2\n3\n\n4\n5\n";
+        assert_eq!(shrink(input.to_owned(), |input| input.contains('4')), "4\n");
+        assert_eq!(shrink(input.to_owned(), |_| false), input);
+    }
+}
@@ -0,0 +1,57 @@
+// Puzzle inputs and recorded answers compiled in with the `embed-inputs` feature. Without the
+// feature these are empty and everything is read from disk.
+
+#[cfg(feature = "embed-inputs")]
+static INPUTS: &[(u8, &str)] = &[
+    (1, include_str!("data/day01.txt")),
+    (2, include_str!("data/day02.txt")),
+    (3, include_str!("data/day03.txt")),
+    (4, include_str!("data/day04.txt")),
+    (5, include_str!("data/day05.txt")),
+    (6, include_str!("data/day06.txt")),
+    (7, include_str!("data/day07.txt")),
+    (8, include_str!("data/day08.txt")),
+    (9, include_str!("data/day09.txt")),
+    (10, include_str!("data/day10.txt")),
+    (11, include_str!("data/day11.txt")),
+];
+
+#[cfg(not(feature = "embed-inputs"))]
+static INPUTS: &[(u8, &str)] = &[];
+
+#[cfg(feature = "embed-inputs")]
+pub const ANSWERS: Option<&str> = Some(include_str!("data/answers.txt"));
+
+#[cfg(not(feature = "embed-inputs"))]
+pub const ANSWERS: Option<&str> = None;
+
+pub fn input(day: u8) -> Option<&'static str> {
+    INPUTS
+        .iter()
+        .find(|(input_day, _)| *input_day == day)
+        .map(|(_, input)| *input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::SOLUTIONS;
+
+    #[test]
+    #[cfg(feature = "embed-inputs")]
+    fn every_day_is_embedded() {
+        for solution in SOLUTIONS {
+            assert!(input(solution.day()).is_some(), "day {}", solution.day());
+        }
+        assert!(ANSWERS.is_some());
+    }
+
+    #[test]
+    #[cfg(not(feature = "embed-inputs"))]
+    fn nothing_is_embedded() {
+        assert!(SOLUTIONS
+            .iter()
+            .all(|solution| input(solution.day()).is_none()));
+        assert_eq!(ANSWERS, None);
+    }
+}
@@ -0,0 +1,192 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result};
+use std::time::Duration;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    // `text` should be a slice of `source` so that its column can be worked out.
+    pub fn new(day: u8, source: &str, text: &str, message: impl Into<String>) -> Self {
+        ParseError {
+            day,
+            line: 1,
+            column: column_of(source, text),
+            text: text.to_owned(),
+            message: message.into(),
+        }
+    }
+
+    pub fn missing(day: u8, source: &str, message: impl Into<String>) -> Self {
+        ParseError::new(day, source, &source[source.len()..], message)
+    }
+
+    pub fn on_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+
+    pub fn offset_lines(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "day {:02}, line {}, column {}: {}",
+            self.day, self.line, self.column, self.message
+        )?;
+        match self.text.as_str() {
+            "" => write!(f, ", found end of line"),
+            text => write!(f, ", found '{}'", text),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+// Why a part produced no answer: bad input, a bug in the solver that panicked (and where, when
+// the panic hook saw it), or a solver that ran past its time budget.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SolveError {
+    Parse(ParseError),
+    Panic {
+        message: String,
+        location: Option<String>,
+    },
+    TimedOut(Duration),
+}
+
+impl From<ParseError> for SolveError {
+    fn from(error: ParseError) -> Self {
+        SolveError::Parse(error)
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            SolveError::Parse(error) => write!(f, "{}", error),
+            SolveError::Panic {
+                message,
+                location: Some(location),
+            } => write!(f, "panicked at {}: {}", location, message),
+            SolveError::Panic {
+                message,
+                location: None,
+            } => write!(f, "panicked: {}", message),
+            SolveError::TimedOut(budget) => write!(f, "timed out after {:?}", budget),
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+fn byte_offset(source: &str, fragment: &str) -> Option<usize> {
+    let start = source.as_ptr() as usize;
+    let position = fragment.as_ptr() as usize;
+    if position >= start && position <= start + source.len() {
+        Some(position - start)
+    } else {
+        None
+    }
+}
+
+// The 1-based character column `text`, a slice of `source`, starts at.
+pub fn column_of(source: &str, text: &str) -> usize {
+    byte_offset(source, text)
+        .and_then(|offset| source.get(..offset))
+        .map_or(1, |before| before.chars().count() + 1)
+}
+
+// Number of lines in `input` before `fragment` starts, for errors raised inside a block.
+pub fn lines_before(input: &str, fragment: &str) -> usize {
+    byte_offset(input, fragment)
+        .and_then(|offset| input.get(..offset))
+        .map_or(0, |before| before.matches('\n').count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn column_from_slice() {
+        let line = "move 3 from x to 2";
+        let error = ParseError::new(5, line, &line[12..13], "expected a stack number");
+        assert_eq!(error.column, 13);
+        assert_eq!(error.text, "x");
+        assert_eq!(error.line, 1);
+    }
+
+    #[test]
+    fn column_at_end_of_line() {
+        let error = ParseError::missing(9, "R", "expected a step count");
+        assert_eq!(error.column, 2);
+        assert_eq!(error.text, "");
+    }
+
+    #[test]
+    fn line_numbers() {
+        let input = "a\nb\n\nc";
+        assert_eq!(lines_before(input, &input[5..]), 3);
+        assert_eq!(lines_before(input, input), 0);
+
+        let error = ParseError::new(1, "c", "c", "oops")
+            .on_line(2)
+            .offset_lines(3);
+        assert_eq!(error.line, 5);
+    }
+
+    #[test]
+    fn solve_error_display() {
+        let error = SolveError::from(ParseError::missing(9, "R", "expected a step count"));
+        assert_eq!(
+            error.to_string(),
+            "day 09, line 1, column 2: expected a step count, found end of line"
+        );
+        assert_eq!(
+            SolveError::Panic {
+                message: String::from("empty stack"),
+                location: None,
+            }
+            .to_string(),
+            "panicked: empty stack"
+        );
+        assert_eq!(
+            SolveError::Panic {
+                message: String::from("empty stack"),
+                location: Some(String::from("src/day05.rs:12:34")),
+            }
+            .to_string(),
+            "panicked at src/day05.rs:12:34: empty stack"
+        );
+        assert_eq!(
+            SolveError::TimedOut(Duration::from_millis(500)).to_string(),
+            "timed out after 500ms"
+        );
+    }
+
+    #[test]
+    fn display() {
+        let line = "Q X";
+        let error = ParseError::new(2, line, &line[0..1], "expected A, B or C").on_line(4);
+        assert_eq!(
+            error.to_string(),
+            "day 02, line 4, column 1: expected A, B or C, found 'Q'"
+        );
+        assert_eq!(
+            ParseError::missing(9, "R", "expected a step count").to_string(),
+            "day 09, line 1, column 2: expected a step count, found end of line"
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use crate::cli::Options;
+use crate::runner::solve_implementation_caught;
+use crate::solution::{get_solution, Part, Solution};
+use crate::verify::{compare, summarize, Outcome};
+
+#[derive(Debug, PartialEq)]
+pub struct ExampleCheck {
+    pub day: u8,
+    // 1-based, in the order the examples are registered.
+    pub example: usize,
+    pub part: Part,
+    pub outcome: Outcome,
+}
+
+impl ExampleCheck {
+    // An example without a known answer only shows what was produced, so it does not fail.
+    pub fn failed(&self) -> bool {
+        matches!(self.outcome, Outcome::Fail { .. } | Outcome::Error(_))
+    }
+}
+
+pub fn check_examples(
+    solution: &dyn Solution,
+    implementation: &str,
+    parts: &[Part],
+) -> Vec<ExampleCheck> {
+    solution
+        .examples()
+        .iter()
+        .enumerate()
+        .flat_map(|(index, example)| {
+            parts.iter().map(move |part| ExampleCheck {
+                day: solution.day(),
+                example: index + 1,
+                part: *part,
+                outcome: compare(
+                    example.expected(*part),
+                    solve_implementation_caught(solution, implementation, *part, example.input),
+                ),
+            })
+        })
+        .collect()
+}
+
+pub fn print_report(checks: &[ExampleCheck]) {
+    println!("Day  Example  Part  Result   {:<24} Actual", "Expected");
+    for check in checks {
+        let (result, expected, actual) = match &check.outcome {
+            Outcome::Pass(actual) => ("pass", summarize(actual), summarize(actual)),
+            Outcome::Fail { expected, actual } => ("FAIL", summarize(expected), summarize(actual)),
+            Outcome::Missing(actual) => ("-", String::from("?"), summarize(actual)),
+            Outcome::Error(message) => ("ERROR", String::new(), message.clone()),
+        };
+        println!(
+            "{:02}   {:<7}  {}     {:<8} {:<24} {}",
+            check.day, check.example, check.part, result, expected, actual
+        );
+    }
+
+    for check in checks {
+        if let Outcome::Fail { expected, actual } = &check.outcome {
+            if expected.contains('\n') || actual.contains('\n') {
+                println!();
+                println!(
+                    "Day {:02} example {} part {} expected:\n{}",
+                    check.day, check.example, check.part, expected
+                );
+                println!(
+                    "Day {:02} example {} part {} actual:\n{}",
+                    check.day, check.example, check.part, actual
+                );
+            }
+        }
+    }
+
+    let count = |outcome: fn(&Outcome) -> bool| {
+        checks
+            .iter()
+            .filter(|check| outcome(&check.outcome))
+            .count()
+    };
+    println!();
+    println!(
+        "{} passed, {} failed, {} without a known answer",
+        count(|outcome| matches!(outcome, Outcome::Pass(_))),
+        count(|outcome| matches!(outcome, Outcome::Fail { .. } | Outcome::Error(_))),
+        count(|outcome| matches!(outcome, Outcome::Missing(_)))
+    );
+}
+
+pub fn run(options: &Options) -> Result<(), String> {
+    let mut checks: Vec<ExampleCheck> = Vec::new();
+    for day in &options.days {
+        let solution = get_solution(*day).ok_or(format!("day {} is not registered", day))?;
+        checks.extend(check_examples(
+            solution,
+            options.implementation(),
+            &options.parts,
+        ));
+    }
+
+    print_report(&checks);
+
+    match checks.iter().filter(|check| check.failed()).count() {
+        0 => Ok(()),
+        failed => Err(format!("{} example checks failed", failed)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day06::Day06;
+    use crate::day10::Day10;
+    use crate::solution::{DEFAULT_IMPLEMENTATION, SOLUTIONS};
+
+    #[test]
+    fn every_day_has_an_example() {
+        for solution in SOLUTIONS {
+            assert!(!solution.examples().is_empty(), "day {}", solution.day());
+        }
+    }
+
+    #[test]
+    fn registered_examples_pass() {
+        for solution in SOLUTIONS {
+            for part in [Part::One, Part::Two] {
+                for implementation in solution.implementation_names(part) {
+                    // A day without known example answers yet, such as one just made with
+                    // new-day, only has to solve them.
+                    for check in check_examples(*solution, implementation, &[part]) {
+                        assert!(
+                            !check.failed(),
+                            "day {} example {} part {} ({}): {:?}",
+                            check.day,
+                            check.example,
+                            check.part,
+                            implementation,
+                            check.outcome
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn checks_are_numbered_per_example() {
+        let checks = check_examples(&Day06, DEFAULT_IMPLEMENTATION, &[Part::Two]);
+        let numbers: Vec<usize> = checks.iter().map(|check| check.example).collect();
+        assert_eq!(numbers, vec![1, 2, 3, 4, 5]);
+        assert!(checks.iter().all(|check| check.part == Part::Two));
+    }
+
+    #[test]
+    fn multi_line_answers_compare_normalized() {
+        let checks = check_examples(&Day10, DEFAULT_IMPLEMENTATION, &[Part::Two]);
+        assert!(!checks[0].failed());
+    }
+}
@@ -0,0 +1,46 @@
+use crate::random::Rng;
+use crate::solution::get_solution;
+
+pub fn run(day: u8, seed: u64, size: usize) -> Result<(), String> {
+    let solution = get_solution(day).ok_or(format!("day {} is not registered", day))?;
+    print!("{}", solution.generate(&mut Rng::new(seed), size));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::random::Rng;
+    use crate::runner::solve_caught;
+    use crate::solution::{Part, SOLUTIONS};
+
+    #[test]
+    fn generated_inputs_solve() {
+        for solution in SOLUTIONS {
+            for (seed, size) in [(1, 0), (2, 1), (3, 10), (4, 60)] {
+                let input = solution.generate(&mut Rng::new(seed), size);
+                for part in [Part::One, Part::Two] {
+                    let answer = solve_caught(*solution, part, &input);
+                    assert!(
+                        answer.is_ok(),
+                        "day {:02} part {} on seed {} size {}: {}\n{}",
+                        solution.day(),
+                        part,
+                        seed,
+                        size,
+                        answer.unwrap_err(),
+                        input
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn the_seed_decides_the_input() {
+        for solution in SOLUTIONS {
+            let generate = |seed| solution.generate(&mut Rng::new(seed), 20);
+            assert_eq!(generate(5), generate(5), "day {:02}", solution.day());
+            assert_ne!(generate(5), generate(6), "day {:02}", solution.day());
+        }
+    }
+}
@@ -0,0 +1,141 @@
+// Puzzle inputs arrive from files saved on any platform, stdin or the examples in the source, so
+// the days read them through these helpers instead of splitting on "\n" themselves. Everything
+// here borrows from the input it is given, so parse errors can still point back into it.
+
+const BYTE_ORDER_MARK: char = '\u{feff}';
+
+// The input without a byte order mark or anything after its last non-blank line.
+pub fn content(input: &str) -> &str {
+    input
+        .strip_prefix(BYTE_ORDER_MARK)
+        .unwrap_or(input)
+        .trim_end()
+}
+
+// The input's lines without their "\n" or "\r\n" endings, up to the last non-blank one.
+pub fn lines(input: &str) -> impl Iterator<Item = &str> {
+    content(input).lines()
+}
+
+// Groups of lines separated by one or more blank lines, such as day 1's elves or day 11's
+// monkeys. Blank lines may hold whitespace; lines inside a block keep theirs, so day 5's
+// drawing stays aligned.
+pub fn blocks(input: &str) -> Blocks<'_> {
+    Blocks {
+        rest: content(input),
+    }
+}
+
+pub struct Blocks<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for Blocks<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let mut start: Option<usize> = None;
+        let mut end = 0;
+        let mut offset = 0;
+
+        for line in self.rest.split_inclusive('\n') {
+            let text = line.trim_end_matches(['\n', '\r']);
+            if !text.trim().is_empty() {
+                start.get_or_insert(offset);
+                end = offset + text.len();
+            } else if start.is_some() {
+                break;
+            }
+            offset += line.len();
+        }
+
+        let rest = self.rest;
+        self.rest = &rest[offset..];
+        start.map(|start| &rest[start..end])
+    }
+}
+
+// What the loaders hand to the days: "\n" line endings, no byte order mark and a single
+// newline after the last non-blank line.
+pub fn normalize(input: &str) -> String {
+    let mut normalized = lines(input).collect::<Vec<&str>>().join("\n");
+    if !normalized.is_empty() {
+        normalized.push('\n');
+    }
+    normalized
+}
+
+// A day made with new-day starts with an empty input file. Such a day has nothing to verify or
+// show in the README until its puzzle input is pasted in.
+pub fn is_blank(input: &str) -> bool {
+    content(input).trim_start().is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::solve_caught;
+    use crate::solution::{Part, SOLUTIONS};
+
+    #[test]
+    fn lines_ignore_line_endings_and_trailing_blank_lines() {
+        let expected = vec!["A Y", "B X", "", "C Z"];
+        assert_eq!(lines("A Y\nB X\n\nC Z\n").collect::<Vec<_>>(), expected);
+        assert_eq!(
+            lines("\u{feff}A Y\r\nB X\r\n\r\nC Z\r\n\r\n  \n").collect::<Vec<_>>(),
+            expected
+        );
+        assert_eq!(lines("\n\n").count(), 0);
+    }
+
+    #[test]
+    fn blocks_split_on_blank_lines() {
+        let input = "\u{feff}1000\r\n2000\r\n\r\n \r\n4000\r\n\r\n\r\n";
+        let found: Vec<&str> = blocks(input).collect();
+        assert_eq!(found, vec!["1000\r\n2000", "4000"]);
+        assert_eq!(crate::error::lines_before(input, found[1]), 4);
+
+        let drawing = "    [D]\n[N] [C]    \n 1   2 \n\nmove 1 from 2 to 1\n";
+        assert_eq!(
+            blocks(drawing).collect::<Vec<_>>(),
+            vec!["    [D]\n[N] [C]    \n 1   2 ", "move 1 from 2 to 1"]
+        );
+        assert_eq!(blocks("").next(), None);
+    }
+
+    #[test]
+    fn normalizing() {
+        assert_eq!(normalize("\u{feff}a\r\n\r\nb \r\n\r\n"), "a\n\nb\n");
+        assert_eq!(normalize("a\nb"), "a\nb\n");
+        assert_eq!(normalize("\r\n"), "");
+    }
+
+    #[test]
+    fn blank_inputs() {
+        assert!(is_blank(""));
+        assert!(is_blank("\u{feff}\r\n  \n"));
+        assert!(!is_blank("\n1000\n"));
+    }
+
+    // Every example with its lines ending in "\r\n", a byte order mark and trailing blank lines
+    // must give the same answers as the original.
+    #[test]
+    fn crlf_examples_give_the_same_answers() {
+        for solution in SOLUTIONS {
+            for example in solution.examples() {
+                let crlf = format!("\u{feff}{}\r\n\r\n", example.input.replace('\n', "\r\n"));
+                for part in [Part::One, Part::Two] {
+                    let expected = solve_caught(*solution, part, example.input);
+                    assert!(expected.is_ok());
+                    assert_eq!(
+                        solve_caught(*solution, part, &crlf),
+                        expected,
+                        "day {:02} part {} with CRLF line endings",
+                        solution.day(),
+                        part
+                    );
+                }
+            }
+        }
+    }
+}
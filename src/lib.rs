@@ -0,0 +1,38 @@
+//! Advent of Code 2022 solutions, one module per day.
+//!
+//! Every day exposes its `parse` function, domain types and `part_one`/`part_two`, and
+//! is registered as a [`solution::Solution`] in [`solution::SOLUTIONS`].
+
+pub mod answer;
+pub mod answers;
+pub mod bench;
+pub mod cli;
+pub mod compare;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod differential;
+pub mod embedded;
+pub mod error;
+pub mod examples;
+pub mod generate;
+pub mod input;
+pub mod lint;
+pub mod memory;
+pub mod ocr;
+pub mod output;
+pub mod random;
+pub mod readme;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod verify;
+pub mod watch;
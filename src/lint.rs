@@ -0,0 +1,148 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::cli::{read_input, Options};
+use crate::error::ParseError;
+use crate::solution::{get_solution, Solution};
+
+// Something wrong with an input, found before any solver gets to trip over it.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Problem {
+    pub day: u8,
+    pub line: usize,
+    pub message: String,
+}
+
+impl Problem {
+    pub fn new(day: u8, line: usize, message: impl Into<String>) -> Self {
+        Problem {
+            day,
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<ParseError> for Problem {
+    fn from(error: ParseError) -> Self {
+        let found = match error.text.as_str() {
+            "" => String::from("end of line"),
+            text => format!("'{}'", text),
+        };
+        Problem::new(
+            error.day,
+            error.line,
+            format!(
+                "{}, found {} at column {}",
+                error.message, found, error.column
+            ),
+        )
+    }
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {:02}, line {}: {}",
+            self.day, self.line, self.message
+        )
+    }
+}
+
+// The day's own checks, then the parser's complaint unless a check already flagged its line:
+// the parser stops at the first problem, so it is usually the same one.
+pub fn lint(solution: &dyn Solution, input: &str) -> Vec<Problem> {
+    let mut problems = solution.lint(input);
+    if let Err(error) = solution.parse(input) {
+        if !problems.iter().any(|problem| problem.line == error.line) {
+            problems.push(Problem::from(error));
+        }
+    }
+    problems.sort_by_key(|problem| problem.line);
+    problems
+}
+
+pub fn run(options: &Options) -> Result<bool, String> {
+    let mut found = 0;
+    for day in &options.days {
+        let solution = get_solution(*day).ok_or(format!("day {} is not registered", day))?;
+        let (input_name, input) = read_input(options, solution)?;
+        for problem in lint(solution, &input) {
+            println!("{}: {}", input_name, problem);
+            found += 1;
+        }
+    }
+
+    match found {
+        0 => println!("No problems found in {} inputs", options.days.len()),
+        1 => println!("\n1 problem found"),
+        found => println!("\n{} problems found", found),
+    }
+    Ok(found == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::read_default_input;
+    use crate::day08::Day08;
+    use crate::random::Rng;
+    use crate::solution::SOLUTIONS;
+
+    #[test]
+    fn inputs_are_clean() {
+        for solution in SOLUTIONS {
+            let (_, input) = read_default_input(*solution).unwrap();
+            assert_eq!(lint(*solution, &input), vec![], "day {:02}", solution.day());
+
+            for example in solution.examples() {
+                assert_eq!(
+                    lint(*solution, example.input),
+                    vec![],
+                    "day {:02}",
+                    solution.day()
+                );
+            }
+
+            for seed in 0..5 {
+                let input = solution.generate(&mut Rng::new(seed), 20);
+                assert_eq!(
+                    lint(*solution, &input),
+                    vec![],
+                    "day {:02} seed {}\n{}",
+                    solution.day(),
+                    seed,
+                    input
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn parse_errors_are_problems_too() {
+        assert_eq!(
+            lint(&Day08, "303\n2a5\n303"),
+            vec![Problem::new(
+                8,
+                2,
+                "expected a tree height, found 'a' at column 2"
+            )]
+        );
+    }
+
+    #[test]
+    fn problems_are_reported_once_per_line() {
+        let problems = lint(&Day08, "30373\n2551\n65332\n335\n");
+        assert_eq!(
+            problems
+                .iter()
+                .map(|problem| problem.line)
+                .collect::<Vec<usize>>(),
+            vec![2, 4]
+        );
+        assert_eq!(
+            problems[0].to_string(),
+            "day 08, line 2: expected a row of 5 trees like the first, found 4"
+        );
+    }
+}
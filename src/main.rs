@@ -1,72 +1,68 @@
-use std::fs::read_to_string;
+use std::env;
+use std::process::ExitCode;
 
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
+use adventofcode_2022_rust::cli::{self, Command, USAGE};
+use adventofcode_2022_rust::memory::CountingAllocator;
+use adventofcode_2022_rust::{generate, lint, readme, scaffold, verify};
 
-fn main() {
-    for (day_index, (part_one_output, part_two_output)) in
-        get_problem_output_strings().iter().enumerate()
-    {
-        println!("########## Day {:02} ##########", day_index + 1);
-        println!("> Part 1 solution: {}", part_one_output);
-        println!("> Part 2 solution: {}", part_two_output);
-    }
-}
+// Lets --mem count allocations.
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
 
-fn get_problem_output_strings() -> Vec<(String, String)> {
-    vec![
-        (
-            day01::part_one(&read_to_string("src/data/day01.txt").unwrap()).to_string(),
-            day01::part_two(&read_to_string("src/data/day01.txt").unwrap()).to_string(),
-        ),
-        (
-            day02::part_one(&read_to_string("src/data/day02.txt").unwrap()).to_string(),
-            day02::part_two(&read_to_string("src/data/day02.txt").unwrap()).to_string(),
-        ),
-        (
-            day03::part_one(&read_to_string("src/data/day03.txt").unwrap()).to_string(),
-            day03::part_two(&read_to_string("src/data/day03.txt").unwrap()).to_string(),
-        ),
-        (
-            day04::part_one(&read_to_string("src/data/day04.txt").unwrap()).to_string(),
-            day04::part_two(&read_to_string("src/data/day04.txt").unwrap()).to_string(),
-        ),
-        (
-            day05::part_one(&read_to_string("src/data/day05.txt").unwrap()),
-            day05::part_two(&read_to_string("src/data/day05.txt").unwrap()),
-        ),
-        (
-            day06::part_one(&read_to_string("src/data/day06.txt").unwrap()).to_string(),
-            day06::part_two(&read_to_string("src/data/day06.txt").unwrap()).to_string(),
-        ),
-        (
-            day07::part_one(&read_to_string("src/data/day07.txt").unwrap()).to_string(),
-            day07::part_two(&read_to_string("src/data/day07.txt").unwrap()).to_string(),
-        ),
-        (
-            day08::part_one(&read_to_string("src/data/day08.txt").unwrap()).to_string(),
-            day08::part_two(&read_to_string("src/data/day08.txt").unwrap()).to_string(),
-        ),
-        (
-            day09::part_one(&read_to_string("src/data/day09.txt").unwrap()).to_string(),
-            day09::part_two(&read_to_string("src/data/day09.txt").unwrap()).to_string(),
-        ),
-        (
-            day10::part_one(&read_to_string("src/data/day10.txt").unwrap()).to_string(),
-            day10::part_two(&read_to_string("src/data/day10.txt").unwrap()).to_string(),
-        ),
-        (
-            day11::part_one(&read_to_string("src/data/day11.txt").unwrap()).to_string(),
-            day11::part_two(&read_to_string("src/data/day11.txt").unwrap()).to_string(),
-        ),
-    ]
+fn main() -> ExitCode {
+    match cli::parse_args(env::args().skip(1)) {
+        Ok(Command::Help) => {
+            println!("{}", USAGE);
+            ExitCode::SUCCESS
+        }
+        Ok(Command::Run(options)) => match cli::run(&options) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(message) => {
+                eprintln!("error: {}", message);
+                ExitCode::FAILURE
+            }
+        },
+        Ok(Command::Verify) => match verify::run() {
+            Ok(true) => ExitCode::SUCCESS,
+            Ok(false) => ExitCode::FAILURE,
+            Err(message) => {
+                eprintln!("error: {}", message);
+                ExitCode::FAILURE
+            }
+        },
+        Ok(Command::Readme { check }) => match readme::run(check) {
+            Ok(true) => ExitCode::SUCCESS,
+            Ok(false) => ExitCode::FAILURE,
+            Err(message) => {
+                eprintln!("error: {}", message);
+                ExitCode::FAILURE
+            }
+        },
+        Ok(Command::NewDay { day, title }) => match scaffold::run(day, title.as_deref()) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(message) => {
+                eprintln!("error: {}", message);
+                ExitCode::FAILURE
+            }
+        },
+        Ok(Command::Generate { day, seed, size }) => match generate::run(day, seed, size) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(message) => {
+                eprintln!("error: {}", message);
+                ExitCode::FAILURE
+            }
+        },
+        Ok(Command::Lint(options)) => match lint::run(&options) {
+            Ok(true) => ExitCode::SUCCESS,
+            Ok(false) => ExitCode::FAILURE,
+            Err(message) => {
+                eprintln!("error: {}", message);
+                ExitCode::FAILURE
+            }
+        },
+        Err(error) => {
+            eprintln!("error: {}\n\n{}", error, USAGE);
+            ExitCode::from(2)
+        }
+    }
 }
@@ -0,0 +1,383 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicUsize, Ordering};
+
+use crate::bench::Stage;
+use crate::cli::{read_input, Format, Options};
+use crate::error::SolveError;
+use crate::output::{csv_field, json_string, stage_failure_message, summarize_failures};
+use crate::runner::catch_panics;
+use crate::solution::{get_solution, solve_named, Part, Solution};
+
+static COUNTING: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+// Memory freed after counting starts may have been allocated before it, so live bytes can dip
+// below where counting started.
+static LIVE: AtomicIsize = AtomicIsize::new(0);
+static PEAK: AtomicIsize = AtomicIsize::new(0);
+
+// Wraps the system allocator and counts what goes through it once `start_counting` is called,
+// so runs without --mem (and --bench in particular) only pay for checking a flag. Only takes
+// effect once a binary installs it with `#[global_allocator]`, which main.rs does.
+pub struct CountingAllocator;
+
+pub fn start_counting() {
+    COUNTING.store(true, Ordering::Relaxed);
+}
+
+fn counting() -> bool {
+    COUNTING.load(Ordering::Relaxed)
+}
+
+fn record_allocation(size: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(size, Ordering::Relaxed);
+    let live = LIVE.fetch_add(size as isize, Ordering::Relaxed) + size as isize;
+    PEAK.fetch_max(live, Ordering::Relaxed);
+}
+
+fn record_free(size: usize) {
+    LIVE.fetch_sub(size as isize, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() && counting() {
+            record_allocation(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        if counting() {
+            record_free(layout.size());
+        }
+    }
+
+    // A reallocation counts as allocating the new size and freeing the old one.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() && counting() {
+            record_free(layout.size());
+            record_allocation(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct Usage {
+    pub allocations: usize,
+    pub bytes: usize,
+    // The most memory live at once above what was live when measuring started.
+    pub peak_bytes: usize,
+}
+
+impl Usage {
+    // Combines stages that ran one after another.
+    fn then(self, other: Usage) -> Usage {
+        Usage {
+            allocations: self.allocations + other.allocations,
+            bytes: self.bytes + other.bytes,
+            peak_bytes: self.peak_bytes.max(other.peak_bytes),
+        }
+    }
+}
+
+// Counts are global, so only one thing may be measured at a time.
+pub fn measure<T>(run: impl FnOnce() -> T) -> (T, Usage) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes = BYTES.load(Ordering::Relaxed);
+    let live = LIVE.load(Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+
+    let result = black_box(run());
+
+    let usage = Usage {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        bytes: BYTES.load(Ordering::Relaxed) - bytes,
+        peak_bytes: (PEAK.load(Ordering::Relaxed) - live).max(0) as usize,
+    };
+    (result, usage)
+}
+
+// Whether CountingAllocator is the global allocator and has started counting, i.e. whether
+// `measure` sees anything.
+pub fn is_counting() -> bool {
+    let (_, usage) = measure(|| Box::new(0u8));
+    usage.allocations > 0
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Scope {
+    Stage(Stage),
+    Total,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct MemoryReport {
+    pub day: u8,
+    pub scope: Scope,
+    // A stage that panics or can't parse the input is recorded with its error.
+    pub usage: Result<Usage, SolveError>,
+}
+
+// Parts are profiled with the named implementation, which must exist for each of them. Each part
+// parses the input itself, so its row includes what the parse row shows, and the total is what
+// the parts cost together, without counting the standalone parse again. There is only a total
+// when every stage succeeded.
+pub fn profile(
+    solution: &dyn Solution,
+    implementation: &str,
+    parts: &[Part],
+    input: &str,
+) -> Vec<MemoryReport> {
+    let mut stages = vec![Stage::Parse];
+    stages.extend(parts.iter().map(|part| Stage::Solve(*part)));
+
+    let mut reports: Vec<MemoryReport> = Vec::new();
+    let mut total = Some(Usage::default());
+    for stage in stages {
+        let (result, usage) = match stage {
+            Stage::Parse => measure(|| catch_panics(|| solution.parse(input))),
+            Stage::Solve(part) => measure(|| {
+                catch_panics(|| solve_named(solution, implementation, part, input).map(|_| ()))
+            }),
+        };
+        let usage = result.map(|_| usage);
+
+        total = match (total, &usage, stage) {
+            (Some(total), Ok(usage), Stage::Solve(_)) => Some(total.then(*usage)),
+            (total, Ok(_), Stage::Parse) => total,
+            _ => None,
+        };
+        reports.push(MemoryReport {
+            day: solution.day(),
+            scope: Scope::Stage(stage),
+            usage,
+        });
+    }
+
+    if let Some(total) = total {
+        reports.push(MemoryReport {
+            day: solution.day(),
+            scope: Scope::Total,
+            usage: Ok(total),
+        });
+    }
+    reports
+}
+
+fn scope_name(scope: Scope) -> String {
+    match scope {
+        Scope::Stage(stage) => stage.to_string(),
+        Scope::Total => String::from("total"),
+    }
+}
+
+fn format_bytes(bytes: usize) -> String {
+    if bytes < 1024 {
+        format!("{} B", bytes)
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KiB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
+
+pub fn print_text(reports: &[MemoryReport]) {
+    println!("Each part parses the input itself, so part rows include parsing and total sums the parts only.");
+    println!(
+        "Day  Stage   {:>12}  {:>12}  {:>12}",
+        "Allocations", "Allocated", "Peak live"
+    );
+    for report in reports {
+        match &report.usage {
+            Ok(usage) => println!(
+                "{:02}   {:<6}  {:>12}  {:>12}  {:>12}",
+                report.day,
+                scope_name(report.scope),
+                usage.allocations,
+                format_bytes(usage.bytes),
+                format_bytes(usage.peak_bytes),
+            ),
+            Err(error) => println!(
+                "{:02}   {:<6}  failed: {}",
+                report.day,
+                scope_name(report.scope),
+                error
+            ),
+        }
+    }
+}
+
+pub fn print_json(reports: &[MemoryReport]) {
+    let records: Vec<String> = reports
+        .iter()
+        .map(|report| {
+            let outcome = match &report.usage {
+                Ok(usage) => format!(
+                    "\"allocations\":{},\"bytes\":{},\"peak_bytes\":{}",
+                    usage.allocations, usage.bytes, usage.peak_bytes,
+                ),
+                Err(error) => format!("\"error\":{}", json_string(&error.to_string())),
+            };
+            format!(
+                "  {{\"day\":{},\"stage\":{},{}}}",
+                report.day,
+                json_string(&scope_name(report.scope)),
+                outcome,
+            )
+        })
+        .collect();
+    println!("[\n{}\n]", records.join(",\n"));
+}
+
+pub fn print_csv(reports: &[MemoryReport]) {
+    println!("day,stage,allocations,bytes,peak_bytes,error");
+    for report in reports {
+        let (counts, error) = match &report.usage {
+            Ok(usage) => (
+                [usage.allocations, usage.bytes, usage.peak_bytes].map(|count| count.to_string()),
+                String::new(),
+            ),
+            Err(error) => (Default::default(), error.to_string()),
+        };
+        println!(
+            "{},{},{},{}",
+            report.day,
+            scope_name(report.scope),
+            counts.join(","),
+            csv_field(&error),
+        );
+    }
+}
+
+pub fn run(options: &Options) -> Result<(), String> {
+    start_counting();
+    if !is_counting() {
+        return Err(String::from(
+            "memory profiling needs CountingAllocator installed as the global allocator",
+        ));
+    }
+
+    let mut reports: Vec<MemoryReport> = Vec::new();
+    let mut failures: Vec<String> = Vec::new();
+    let mut stages = 0;
+    for day in &options.days {
+        let solution = get_solution(*day).ok_or(format!("day {} is not registered", day))?;
+        let (input_name, input) = read_input(options, solution)?;
+
+        for report in profile(solution, options.implementation(), &options.parts, &input) {
+            if let Scope::Stage(stage) = report.scope {
+                stages += 1;
+                if let Err(error) = &report.usage {
+                    failures.push(stage_failure_message(
+                        report.day,
+                        &stage.to_string(),
+                        &input_name,
+                        error,
+                    ));
+                }
+            }
+            reports.push(report);
+        }
+    }
+
+    match options.format {
+        Format::Text => print_text(&reports),
+        Format::Json => print_json(&reports),
+        Format::Csv => print_csv(&reports),
+    }
+
+    match summarize_failures(failures, stages, "stages") {
+        Some(summary) => Err(summary),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day01::Day01;
+    use crate::solution::DEFAULT_IMPLEMENTATION;
+
+    // The test harness runs with the system allocator, so nothing is counted here; the
+    // counting itself is exercised through the binary.
+    #[test]
+    fn not_counting_without_the_global_allocator() {
+        assert!(!is_counting());
+        let (value, usage) = measure(|| vec![1u64; 100]);
+        assert_eq!(value.len(), 100);
+        assert_eq!(usage, Usage::default());
+    }
+
+    #[test]
+    fn profiling_every_stage() {
+        let reports = profile(
+            &Day01,
+            DEFAULT_IMPLEMENTATION,
+            &[Part::One, Part::Two],
+            "1000\n\n2000",
+        );
+        let scopes: Vec<Scope> = reports.iter().map(|report| report.scope).collect();
+        assert_eq!(
+            scopes,
+            vec![
+                Scope::Stage(Stage::Parse),
+                Scope::Stage(Stage::Solve(Part::One)),
+                Scope::Stage(Stage::Solve(Part::Two)),
+                Scope::Total
+            ]
+        );
+        assert!(reports.iter().all(|report| report.usage.is_ok()));
+    }
+
+    #[test]
+    fn failing_stages_are_recorded() {
+        let reports = profile(&Day01, DEFAULT_IMPLEMENTATION, &[Part::One], "10x0");
+        let scopes: Vec<Scope> = reports.iter().map(|report| report.scope).collect();
+        assert_eq!(
+            scopes,
+            vec![
+                Scope::Stage(Stage::Parse),
+                Scope::Stage(Stage::Solve(Part::One))
+            ]
+        );
+        assert!(reports
+            .iter()
+            .all(|report| matches!(report.usage, Err(SolveError::Parse(_)))));
+    }
+
+    #[test]
+    fn combining_usage() {
+        let first = Usage {
+            allocations: 2,
+            bytes: 100,
+            peak_bytes: 80,
+        };
+        let second = Usage {
+            allocations: 1,
+            bytes: 10,
+            peak_bytes: 90,
+        };
+        assert_eq!(
+            first.then(second),
+            Usage {
+                allocations: 3,
+                bytes: 110,
+                peak_bytes: 90
+            }
+        );
+    }
+
+    #[test]
+    fn formatting_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(2048), "2.0 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
@@ -0,0 +1,220 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+// Letters in AoC's 4x6 font are drawn with one unlit column between them.
+pub const GLYPH_WIDTH: usize = 4;
+pub const GLYPH_HEIGHT: usize = 6;
+const GLYPH_SPACING: usize = GLYPH_WIDTH + 1;
+
+// Every letter the puzzles are known to use.
+const FONT: &[(char, [&str; GLYPH_HEIGHT])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+// Every error but `UnknownGlyph` means the grid isn't shaped like letters at all.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum OcrError {
+    Height(usize),
+    Width(usize),
+    // `column` and `index` (of a glyph) count from 1; `glyph` is the unrecognised pixels as
+    // '#'/'.' rows.
+    LitSpacer { column: usize },
+    Blank { index: usize },
+    UnknownGlyph { index: usize, glyph: String },
+}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            OcrError::Height(height) => write!(
+                f,
+                "expected a grid {} pixels high, found {}",
+                GLYPH_HEIGHT, height
+            ),
+            OcrError::Width(width) => write!(
+                f,
+                "expected a grid of {} pixel wide letters {} apart, found a width of {}",
+                GLYPH_WIDTH, GLYPH_SPACING, width
+            ),
+            OcrError::LitSpacer { column } => {
+                write!(f, "column {} between letters is lit", column)
+            }
+            OcrError::Blank { index } => write!(f, "letter {} is blank", index),
+            OcrError::UnknownGlyph { index, glyph } => {
+                write!(f, "letter {} is not in the font:\n{}", index, glyph)
+            }
+        }
+    }
+}
+
+impl Error for OcrError {}
+
+fn render(rows: &[&[bool]]) -> Vec<String> {
+    rows.iter()
+        .map(|row| row.iter().map(|lit| if *lit { '#' } else { '.' }).collect())
+        .collect()
+}
+
+// Reads a grid of lit pixels, one row per entry, as the letters drawn on it.
+pub fn read_letters(grid: &[Vec<bool>]) -> Result<String, OcrError> {
+    if grid.len() != GLYPH_HEIGHT {
+        return Err(OcrError::Height(grid.len()));
+    }
+
+    let width = grid[0].len();
+    if grid.iter().any(|row| row.len() != width)
+        || width == 0
+        || !(width.is_multiple_of(GLYPH_SPACING) || (width + 1).is_multiple_of(GLYPH_SPACING))
+    {
+        return Err(OcrError::Width(width));
+    }
+
+    let glyphs: Vec<Vec<&[bool]>> = (0..width.div_ceil(GLYPH_SPACING))
+        .map(|index| {
+            let start = index * GLYPH_SPACING;
+            grid.iter()
+                .map(|row| &row[start..start + GLYPH_WIDTH])
+                .collect()
+        })
+        .collect();
+
+    // The whole grid must be letter-shaped before any letter is read, so a screen that isn't is
+    // never reported as an unknown letter.
+    let mut spacers = (GLYPH_WIDTH..width).step_by(GLYPH_SPACING);
+    if let Some(column) = spacers.find(|column| grid.iter().any(|row| row[*column])) {
+        return Err(OcrError::LitSpacer { column: column + 1 });
+    }
+    if let Some(index) = glyphs
+        .iter()
+        .position(|glyph| glyph.iter().all(|row| !row.contains(&true)))
+    {
+        return Err(OcrError::Blank { index: index + 1 });
+    }
+
+    glyphs
+        .iter()
+        .enumerate()
+        .map(|(index, glyph)| {
+            let glyph = render(glyph);
+
+            FONT.iter()
+                .find(|(_, pixels)| pixels.iter().eq(glyph.iter()))
+                .map(|(letter, _)| *letter)
+                .ok_or_else(|| OcrError::UnknownGlyph {
+                    index: index + 1,
+                    glyph: glyph.join("\n"),
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(rows: &[&str]) -> Vec<Vec<bool>> {
+        rows.iter()
+            .map(|row| row.chars().map(|c| c == '#').collect())
+            .collect()
+    }
+
+    #[test]
+    fn reading_the_day_10_screen() {
+        let screen = grid(&[
+            "###..####.#..#.###..###..#....#..#.###..",
+            "#..#.#....#..#.#..#.#..#.#....#..#.#..#.",
+            "#..#.###..####.#..#.#..#.#....#..#.###..",
+            "###..#....#..#.###..###..#....#..#.#..#.",
+            "#.#..#....#..#.#....#.#..#....#..#.#..#.",
+            "#..#.####.#..#.#....#..#.####..##..###..",
+        ]);
+        assert_eq!(read_letters(&screen), Ok(String::from("REHPRLUB")));
+    }
+
+    #[test]
+    fn the_last_spacer_column_is_optional() {
+        let letters = grid(&[
+            "#..#.####",
+            "#..#.#...",
+            "####.###.",
+            "#..#.#...",
+            "#..#.#...",
+            "#..#.####",
+        ]);
+        assert_eq!(read_letters(&letters), Ok(String::from("HE")));
+    }
+
+    #[test]
+    fn every_letter_in_the_font_reads_back() {
+        for (letter, pixels) in FONT {
+            assert_eq!(read_letters(&grid(pixels)), Ok(letter.to_string()));
+        }
+    }
+
+    #[test]
+    fn unknown_glyphs() {
+        let letters = grid(&[
+            "#..#.####",
+            "#..#.####",
+            "####.####",
+            "#..#.####",
+            "#..#.####",
+            "#..#.####",
+        ]);
+        let error = read_letters(&letters).unwrap_err();
+        assert_eq!(
+            error,
+            OcrError::UnknownGlyph {
+                index: 2,
+                glyph: String::from("####\n####\n####\n####\n####\n####")
+            }
+        );
+        assert!(error
+            .to_string()
+            .starts_with("letter 2 is not in the font:\n####"));
+    }
+
+    #[test]
+    fn grids_that_are_not_letters() {
+        let stripes = grid(&["##..##..##"; 6]);
+        assert_eq!(
+            read_letters(&stripes),
+            Err(OcrError::LitSpacer { column: 5 })
+        );
+        let gap = grid(&[
+            "#..#.....",
+            "#..#.....",
+            "####.....",
+            "#..#.....",
+            "#..#.....",
+            "#..#.....",
+        ]);
+        assert_eq!(read_letters(&gap), Err(OcrError::Blank { index: 2 }));
+        assert_eq!(
+            OcrError::LitSpacer { column: 5 }.to_string(),
+            "column 5 between letters is lit"
+        );
+    }
+
+    #[test]
+    fn grid_size_errors() {
+        assert_eq!(read_letters(&grid(&["#..#"])), Err(OcrError::Height(1)));
+        assert_eq!(read_letters(&grid(&["#..#.."; 6])), Err(OcrError::Width(6)));
+    }
+}
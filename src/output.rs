@@ -0,0 +1,337 @@
+use crate::answer::Answer;
+use crate::error::SolveError;
+use crate::runner::{DayResult, PartResult};
+use crate::solution::Part;
+
+// `stage` names what failed, such as "part 1", or "parse" when benchmarking.
+pub fn stage_failure_message(day: u8, stage: &str, input_name: &str, error: &SolveError) -> String {
+    match error {
+        SolveError::Parse(error) => format!("could not parse {}: {}", input_name, error),
+        SolveError::Panic { message, location } => format!(
+            "day {:02} {} panicked on {}{}: {}",
+            day,
+            stage,
+            input_name,
+            location
+                .as_ref()
+                .map_or(String::new(), |location| format!(" at {}", location)),
+            message
+        ),
+        SolveError::TimedOut(budget) => format!(
+            "day {:02} {} timed out on {} after {:?}",
+            day, stage, input_name, budget
+        ),
+    }
+}
+
+pub fn failure_message(result: &DayResult, part: Part, error: &SolveError) -> String {
+    stage_failure_message(
+        result.solution.day(),
+        &format!("part {}", part),
+        &result.input_name,
+        error,
+    )
+}
+
+pub fn failures(results: &[DayResult]) -> Vec<String> {
+    results
+        .iter()
+        .flat_map(|result| {
+            result
+                .parts
+                .iter()
+                .filter_map(move |part| match &part.answer {
+                    Ok(_) => None,
+                    Err(error) => Some(failure_message(result, part.part, error)),
+                })
+        })
+        .collect()
+}
+
+pub fn first_error(results: &[DayResult]) -> Option<String> {
+    failures(results).into_iter().next()
+}
+
+// Lists every part that failed, for the end of a run that carried on past them.
+pub fn failure_summary(results: &[DayResult]) -> Option<String> {
+    let total: usize = results.iter().map(|result| result.parts.len()).sum();
+    summarize_failures(failures(results), total, "parts")
+}
+
+// As `failure_summary`, for runs that measure other things than parts, such as stages.
+pub fn summarize_failures(failures: Vec<String>, total: usize, what: &str) -> Option<String> {
+    if failures.is_empty() {
+        return None;
+    }
+
+    let mut summary = format!("{} of {} {} failed:", failures.len(), total, what);
+    for failure in failures {
+        summary.push_str(&format!("\n  {}", failure));
+    }
+    Some(summary)
+}
+
+pub fn print_text(results: &[DayResult]) {
+    let mut current_user: Option<&str> = None;
+    for result in results {
+        if let Some(user) = result.user.as_deref() {
+            if current_user != Some(user) {
+                println!("========== Inputs: {} ==========", user);
+                current_user = Some(user);
+            }
+        }
+        println!(
+            "########## Day {:02}: {} ##########",
+            result.solution.day(),
+            result.solution.title()
+        );
+        for part in &result.parts {
+            let answer = match &part.answer {
+                Ok(answer) => answer,
+                Err(error) => {
+                    println!("> Part {} failed: {}", part.part, error);
+                    continue;
+                }
+            };
+            if answer.is_multi_line() {
+                println!("> Part {} solution:", part.part);
+                for line in answer.lines() {
+                    println!("    {}", line);
+                }
+            } else {
+                println!("> Part {} solution: {}", part.part, answer);
+            }
+        }
+    }
+}
+
+pub fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
+    }
+}
+
+// Integers are numbers and multi-line answers such as day 10's screen an array of rows.
+pub fn json_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Integer(value) => value.to_string(),
+        answer if answer.is_multi_line() => {
+            let rows: Vec<String> = answer.lines().iter().map(|row| json_string(row)).collect();
+            format!("[{}]", rows.join(","))
+        }
+        answer => json_string(&answer.to_string()),
+    }
+}
+
+fn json_record(result: &DayResult, part: &PartResult) -> String {
+    let outcome = match &part.answer {
+        Ok(answer) => format!("\"answer\":{}", json_answer(answer)),
+        Err(error) => format!("\"error\":{}", json_string(&error.to_string())),
+    };
+    let user = match &result.user {
+        Some(user) => format!("\"user\":{},", json_string(user)),
+        None => String::new(),
+    };
+    format!(
+        "{{{}\"day\":{},\"title\":{},\"part\":{},{},\"elapsed_ns\":{}}}",
+        user,
+        result.solution.day(),
+        json_string(result.solution.title()),
+        part.part,
+        outcome,
+        part.elapsed.as_nanos()
+    )
+}
+
+pub fn format_json(results: &[DayResult]) -> String {
+    let records: Vec<String> = results
+        .iter()
+        .flat_map(|result| {
+            result
+                .parts
+                .iter()
+                .map(move |part| format!("  {}", json_record(result, part)))
+        })
+        .collect();
+    format!("[\n{}\n]", records.join(",\n"))
+}
+
+// The user column only appears when running per-user input sets.
+pub fn format_csv(results: &[DayResult]) -> String {
+    let with_users = results.iter().any(|result| result.user.is_some());
+    let user_column = if with_users { "user," } else { "" };
+    let mut lines = vec![format!(
+        "{}day,title,part,answer,error,elapsed_ns",
+        user_column
+    )];
+    for result in results {
+        let user = match (with_users, &result.user) {
+            (true, Some(user)) => format!("{},", csv_field(user)),
+            (true, None) => String::from(","),
+            (false, _) => String::new(),
+        };
+        for part in &result.parts {
+            let (answer, error) = match &part.answer {
+                Ok(answer) => (answer.to_string(), String::new()),
+                Err(error) => (String::new(), error.to_string()),
+            };
+            lines.push(format!(
+                "{}{},{},{},{},{},{}",
+                user,
+                result.solution.day(),
+                csv_field(result.solution.title()),
+                part.part,
+                csv_field(&answer),
+                csv_field(&error),
+                part.elapsed.as_nanos()
+            ));
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day01::Day01;
+    use crate::day10::Day10;
+    use crate::error::ParseError;
+    use crate::solution::Part;
+    use std::time::Duration;
+
+    fn results() -> Vec<DayResult> {
+        vec![
+            DayResult {
+                solution: &Day01,
+                user: None,
+                input_name: String::from("day01"),
+                parts: vec![
+                    PartResult {
+                        part: Part::One,
+                        answer: Ok(Answer::Integer(24000)),
+                        elapsed: Duration::from_nanos(1500),
+                    },
+                    PartResult {
+                        part: Part::Two,
+                        answer: Err(SolveError::from(ParseError::new(
+                            1,
+                            "1x",
+                            "1x",
+                            "expected a calorie count",
+                        ))),
+                        elapsed: Duration::from_nanos(20),
+                    },
+                ],
+            },
+            DayResult {
+                solution: &Day10,
+                user: None,
+                input_name: String::from("day10"),
+                parts: vec![PartResult {
+                    part: Part::Two,
+                    answer: Ok(Answer::parse("##..\n#..#")),
+                    elapsed: Duration::from_nanos(7),
+                }],
+            },
+        ]
+    }
+
+    #[test]
+    fn finding_errors() {
+        assert_eq!(
+            first_error(&results()),
+            Some(String::from(
+                "could not parse day01: day 01, line 1, column 1: expected a calorie count, found '1x'"
+            ))
+        );
+        assert_eq!(first_error(&results()[1..]), None);
+    }
+
+    #[test]
+    fn summarizing_failures() {
+        let mut results = results();
+        results[1].parts[0].answer = Err(SolveError::Panic {
+            message: String::from("empty stack"),
+            location: Some(String::from("src/day10.rs:40:5")),
+        });
+        assert_eq!(
+            failure_summary(&results),
+            Some(String::from(
+                "2 of 3 parts failed:
+  could not parse day01: day 01, line 1, column 1: expected a calorie count, found '1x'
+  day 10 part 2 panicked on day10 at src/day10.rs:40:5: empty stack"
+            ))
+        );
+        assert_eq!(failure_summary(&results[..0]), None);
+    }
+
+    #[test]
+    fn escaping() {
+        assert_eq!(json_string("a \"b\"\\\n"), "\"a \\\"b\\\"\\\\\\n\"");
+        assert_eq!(json_string("\u{1}"), "\"\\u0001\"");
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+    }
+
+    #[test]
+    fn json_output() {
+        assert_eq!(
+            format_json(&results()),
+            r###"[
+  {"day":1,"title":"Calorie Counting","part":1,"answer":24000,"elapsed_ns":1500},
+  {"day":1,"title":"Calorie Counting","part":2,"error":"day 01, line 1, column 1: expected a calorie count, found '1x'","elapsed_ns":20},
+  {"day":10,"title":"Cathode-Ray Tube","part":2,"answer":["##..","#..#"],"elapsed_ns":7}
+]"###
+        );
+    }
+
+    #[test]
+    fn csv_output() {
+        assert_eq!(
+            format_csv(&results()),
+            "day,title,part,answer,error,elapsed_ns
+1,Calorie Counting,1,24000,,1500
+1,Calorie Counting,2,,\"day 01, line 1, column 1: expected a calorie count, found '1x'\",20
+10,Cathode-Ray Tube,2,\"##..\n#..#\",,7"
+        );
+    }
+
+    #[test]
+    fn user_column() {
+        let mut results = results();
+        results.truncate(1);
+        results[0].user = Some(String::from("alice"));
+        results[0].parts.truncate(1);
+
+        assert_eq!(
+            format_json(&results),
+            r###"[
+  {"user":"alice","day":1,"title":"Calorie Counting","part":1,"answer":24000,"elapsed_ns":1500}
+]"###
+        );
+        assert_eq!(
+            format_csv(&results),
+            "user,day,title,part,answer,error,elapsed_ns\nalice,1,Calorie Counting,1,24000,,1500"
+        );
+    }
+}
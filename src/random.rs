@@ -0,0 +1,103 @@
+use std::ops::RangeInclusive;
+
+// A small seeded generator (splitmix64) for building puzzle inputs. Not for anything that needs
+// real randomness, but the same seed gives the same input on every platform and build.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // A number below `bound`, without the bias of a plain `%`.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "cannot pick a number below 0");
+        let zone = u64::MAX - u64::MAX % bound;
+        loop {
+            let value = self.next_u64();
+            if value < zone {
+                return value % bound;
+            }
+        }
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "empty range {}..={}", start, end);
+        let span = end.abs_diff(start);
+        match span.checked_add(1) {
+            Some(bound) => start.wrapping_add(self.below(bound) as i64),
+            None => self.next_u64() as i64,
+        }
+    }
+
+    pub fn index(&mut self, len: usize) -> usize {
+        self.below(len as u64) as usize
+    }
+
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for end in (1..items.len()).rev() {
+            items.swap(end, self.index(end + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_numbers() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..5).map(|_| rng.next_u64()).collect::<Vec<u64>>()
+        };
+        assert_eq!(numbers(7), numbers(7));
+        assert_ne!(numbers(7), numbers(8));
+    }
+
+    #[test]
+    fn values_stay_in_range() {
+        let mut rng = Rng::new(1);
+        let mut seen = [false; 7];
+        for _ in 0..1000 {
+            let value = rng.range(-3..=3);
+            assert!((-3..=3).contains(&value));
+            seen[(value + 3) as usize] = true;
+        }
+        assert!(seen.iter().all(|seen| *seen));
+
+        assert_eq!(rng.range(5..=5), 5);
+        rng.range(i64::MIN..=i64::MAX);
+        assert!(rng.below(10) < 10);
+    }
+
+    #[test]
+    fn shuffling_keeps_every_item() {
+        let mut rng = Rng::new(3);
+        let mut items: Vec<u32> = (0..20).collect();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..20).collect::<Vec<u32>>());
+        items.sort();
+        assert_eq!(items, (0..20).collect::<Vec<u32>>());
+    }
+}
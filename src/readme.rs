@@ -0,0 +1,177 @@
+use std::fs::{read_to_string, write};
+use std::path::PathBuf;
+
+use crate::cli::{read_jobs, Options};
+use crate::input::is_blank;
+use crate::output::first_error;
+use crate::runner::{solve, DayResult};
+use crate::solution::{DEFAULT_IMPLEMENTATION, DEFAULT_INPUT_DIR};
+
+pub const README_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/README.md");
+
+const RESULTS_START: &str = "<summary>⚠️ Current output (Spoilers!!!) ⚠️</summary>";
+const RESULTS_END: &str = "</details>";
+
+// Renders the list that sits inside the README's spoiler block. Multi-line answers such as
+// day 10's screen are put in an indented code fence under their part.
+pub fn render_results(results: &[DayResult]) -> Result<String, String> {
+    if let Some(error) = first_error(results) {
+        return Err(error);
+    }
+
+    let mut lines: Vec<String> = Vec::new();
+    for result in results {
+        lines.push(format!("- Day {:02}", result.solution.day()));
+        for part in &result.parts {
+            let answer = part.answer.as_ref().unwrap();
+            if answer.is_multi_line() {
+                lines.push(format!("  - Part {} solution:", part.part));
+                lines.push(String::from("    ```"));
+                lines.extend(answer.lines().iter().map(|line| format!("    {}", line)));
+                lines.push(String::from("    ```"));
+            } else {
+                lines.push(format!("  - Part {} solution: {}", part.part, answer));
+            }
+        }
+    }
+    Ok(lines.join("\n"))
+}
+
+// Swaps whatever is between the spoiler summary and the closing tag for `results`, leaving
+// the rest of the README alone.
+pub fn replace_results(readme: &str, results: &str) -> Result<String, String> {
+    let start = readme
+        .find(RESULTS_START)
+        .map(|index| index + RESULTS_START.len())
+        .ok_or(format!("could not find '{}'", RESULTS_START))?;
+    let end = readme[start..]
+        .find(RESULTS_END)
+        .map(|index| start + index)
+        .ok_or(format!(
+            "could not find '{}' after the results",
+            RESULTS_END
+        ))?;
+
+    Ok(format!(
+        "{}\n\n{}\n\n{}",
+        &readme[..start],
+        results,
+        &readme[end..]
+    ))
+}
+
+pub fn run(check: bool) -> Result<bool, String> {
+    // Always the default inputs, whatever the input directory variable says.
+    let options = Options {
+        input_dir: Some(PathBuf::from(DEFAULT_INPUT_DIR)),
+        ..Options::default()
+    };
+    // A day whose input is still empty has no results to show yet.
+    let jobs = read_jobs(&options)?
+        .into_iter()
+        .filter(|job| !is_blank(&job.input))
+        .collect();
+    let results = solve(jobs, DEFAULT_IMPLEMENTATION, &options.parts, true, None);
+    let block = render_results(&results)?;
+
+    let readme = read_to_string(README_PATH)
+        .map_err(|error| format!("could not read {}: {}", README_PATH, error))?;
+    let updated = replace_results(&readme, &block)?;
+
+    if check {
+        let current = updated == readme;
+        if current {
+            println!("{} is up to date", README_PATH);
+        } else {
+            println!(
+                "{} is out of date, run the readme command to update it",
+                README_PATH
+            );
+        }
+        Ok(current)
+    } else {
+        if updated != readme {
+            write(README_PATH, updated)
+                .map_err(|error| format!("could not write {}: {}", README_PATH, error))?;
+        }
+        println!("Updated {}", README_PATH);
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answer::Answer;
+    use crate::day01::Day01;
+    use crate::day10::Day10;
+    use crate::error::SolveError;
+    use crate::runner::PartResult;
+    use crate::solution::Part;
+    use std::time::Duration;
+
+    fn result(solution: &'static dyn crate::solution::Solution, answers: &[&str]) -> DayResult {
+        DayResult {
+            solution,
+            user: None,
+            input_name: String::from("test"),
+            parts: answers
+                .iter()
+                .zip([Part::One, Part::Two])
+                .map(|(answer, part)| PartResult {
+                    part,
+                    answer: Ok(Answer::parse(answer)),
+                    elapsed: Duration::ZERO,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn rendering() {
+        let results = vec![
+            result(&Day01, &["24000", "45000"]),
+            result(&Day10, &["13140", "\n        ##..\n        #..#\n"]),
+        ];
+        assert_eq!(
+            render_results(&results),
+            Ok(String::from(
+                "- Day 01
+  - Part 1 solution: 24000
+  - Part 2 solution: 45000
+- Day 10
+  - Part 1 solution: 13140
+  - Part 2 solution:
+    ```
+    ##..
+    #..#
+    ```"
+            ))
+        );
+    }
+
+    #[test]
+    fn rendering_errors() {
+        let mut results = vec![result(&Day01, &["24000"])];
+        results[0].parts[0].answer = Err(SolveError::Panic {
+            message: String::from("oops"),
+            location: None,
+        });
+        assert!(render_results(&results).is_err());
+    }
+
+    #[test]
+    fn replacing() {
+        let readme = format!(
+            "# Title\n\n<details>\n{}\n\n- Day 01\n  - Part 1 solution: 1\n\n</details>\n\nMore text\n",
+            RESULTS_START
+        );
+        let updated = replace_results(&readme, "- Day 01\n  - Part 1 solution: 2").unwrap();
+        assert_eq!(updated, readme.replace("solution: 1", "solution: 2"));
+        assert_eq!(
+            replace_results(&updated, "- Day 01\n  - Part 1 solution: 2"),
+            Ok(updated)
+        );
+        assert!(replace_results("# Title\n", "").is_err());
+    }
+}
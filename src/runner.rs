@@ -0,0 +1,510 @@
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Once;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::answer::Answer;
+use crate::error::{ParseError, SolveError};
+use crate::solution::{solve_named, Part, Solution};
+
+pub struct Job {
+    pub solution: &'static dyn Solution,
+    // The input set the job belongs to when running everyone's inputs.
+    pub user: Option<String>,
+    pub input_name: String,
+    pub input: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct PartResult {
+    pub part: Part,
+    pub answer: Result<Answer, SolveError>,
+    pub elapsed: Duration,
+}
+
+pub struct DayResult {
+    pub solution: &'static dyn Solution,
+    pub user: Option<String>,
+    pub input_name: String,
+    pub parts: Vec<PartResult>,
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => String::from("unknown panic"),
+        },
+    }
+}
+
+thread_local! {
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+static QUIET_HOOK: Once = Once::new();
+
+// The panic hook is global but solvers run on many threads, so the hook stays installed and only
+// keeps quiet on a thread that is inside `catch_panics`, noting where the panic happened instead
+// of printing it. Panics anywhere else still go to the previous hook.
+fn install_quiet_hook() {
+    QUIET_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.with(Cell::get) {
+                let location = info.location().map(|location| location.to_string());
+                PANIC_LOCATION.with(|cell| *cell.borrow_mut() = location);
+            } else {
+                previous(info);
+            }
+        }));
+    });
+}
+
+// Runs a solver, or anything that calls one such as a benchmark, behind a panic boundary,
+// turning a panic into an error.
+pub fn catch_panics<T>(solve: impl FnOnce() -> Result<T, ParseError>) -> Result<T, SolveError> {
+    install_quiet_hook();
+    let was_catching = CATCHING.with(|catching| catching.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(solve));
+    CATCHING.with(|catching| catching.set(was_catching));
+
+    match result {
+        Ok(answer) => Ok(answer?),
+        Err(payload) => Err(SolveError::Panic {
+            message: panic_message(payload),
+            location: PANIC_LOCATION.with(RefCell::take),
+        }),
+    }
+}
+
+// Solves a part behind a panic boundary, so a bug in one day doesn't take down the others.
+pub fn solve_caught(
+    solution: &dyn Solution,
+    part: Part,
+    input: &str,
+) -> Result<Answer, SolveError> {
+    catch_panics(|| solution.solve(part, input))
+}
+
+// As `solve_caught`, with one of the day's named implementations.
+pub fn solve_implementation_caught(
+    solution: &dyn Solution,
+    implementation: &str,
+    part: Part,
+    input: &str,
+) -> Result<Answer, SolveError> {
+    catch_panics(|| solve_named(solution, implementation, part, input))
+}
+
+// Solves a part on a worker thread and gives up on it once `budget` has passed. Threads can't be
+// stopped, so an overrunning solver is left to finish in the background, or until the process
+// exits, while the run moves on; it then no longer counts towards the cap on parallel parts.
+fn solve_with_budget(
+    solution: &'static dyn Solution,
+    implementation: &str,
+    part: Part,
+    input: &str,
+    budget: Duration,
+) -> Result<Answer, SolveError> {
+    let (sender, receiver) = mpsc::channel();
+    let (implementation, input) = (implementation.to_owned(), input.to_owned());
+    thread::spawn(move || {
+        // Nobody is listening any more if the part already timed out.
+        let _ = sender.send(solve_implementation_caught(
+            solution,
+            &implementation,
+            part,
+            &input,
+        ));
+    });
+
+    match receiver.recv_timeout(budget) {
+        Ok(answer) => answer,
+        Err(RecvTimeoutError::Timeout) => Err(SolveError::TimedOut(budget)),
+        Err(RecvTimeoutError::Disconnected) => Err(SolveError::Panic {
+            message: String::from("worker thread exited without an answer"),
+            location: None,
+        }),
+    }
+}
+
+fn solve_part(
+    solution: &'static dyn Solution,
+    implementation: &str,
+    part: Part,
+    input: &str,
+    timeout: Option<Duration>,
+) -> PartResult {
+    let start = Instant::now();
+    let answer = match timeout {
+        Some(budget) => solve_with_budget(solution, implementation, part, input, budget),
+        None => solve_implementation_caught(solution, implementation, part, input),
+    };
+    PartResult {
+        part,
+        answer,
+        elapsed: start.elapsed(),
+    }
+}
+
+fn solve_sequential(
+    jobs: &[Job],
+    implementation: &str,
+    parts: &[Part],
+    timeout: Option<Duration>,
+) -> Vec<Vec<PartResult>> {
+    jobs.iter()
+        .map(|job| {
+            parts
+                .iter()
+                .map(|part| solve_part(job.solution, implementation, *part, &job.input, timeout))
+                .collect()
+        })
+        .collect()
+}
+
+// Days and parts are shared out among at most one thread per core; results are put back in job
+// order.
+fn solve_parallel(
+    jobs: &[Job],
+    implementation: &str,
+    parts: &[Part],
+    timeout: Option<Duration>,
+) -> Vec<Vec<PartResult>> {
+    let tasks: Vec<(&Job, Part)> = jobs
+        .iter()
+        .flat_map(|job| parts.iter().map(move |part| (job, *part)))
+        .collect();
+    let next = AtomicUsize::new(0);
+    let workers = thread::available_parallelism()
+        .map_or(1, |cores| cores.get())
+        .min(tasks.len());
+
+    // Each worker takes the next unsolved part until none are left.
+    let mut solved: Vec<(usize, PartResult)> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut solved = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some((job, part)) = tasks.get(index) else {
+                            return solved;
+                        };
+                        let result =
+                            solve_part(job.solution, implementation, *part, &job.input, timeout);
+                        solved.push((index, result));
+                    }
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|error| panic::resume_unwind(error))
+            })
+            .collect()
+    });
+    solved.sort_by_key(|(index, _)| *index);
+
+    let mut solved = solved.into_iter().map(|(_, result)| result);
+    jobs.iter()
+        .map(|_| solved.by_ref().take(parts.len()).collect())
+        .collect()
+}
+
+pub fn solve(
+    jobs: Vec<Job>,
+    implementation: &str,
+    parts: &[Part],
+    parallel: bool,
+    timeout: Option<Duration>,
+) -> Vec<DayResult> {
+    let results = if parallel {
+        solve_parallel(&jobs, implementation, parts, timeout)
+    } else {
+        solve_sequential(&jobs, implementation, parts, timeout)
+    };
+
+    jobs.into_iter()
+        .zip(results)
+        .map(|(job, parts)| DayResult {
+            solution: job.solution,
+            user: job.user,
+            input_name: job.input_name,
+            parts,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day01::Day01;
+    use crate::day03::{self, Day03};
+    use crate::day04::Day04;
+    use crate::random::Rng;
+    use crate::solution::{Example, DEFAULT_IMPLEMENTATION};
+
+    fn jobs() -> Vec<Job> {
+        vec![
+            Job {
+                solution: &Day04,
+                user: None,
+                input_name: String::from("day04"),
+                input: String::from("2-4,6-8\n2-8,3-7\n6-6,4-6"),
+            },
+            Job {
+                solution: &Day01,
+                user: Some(String::from("alice")),
+                input_name: String::from("day01"),
+                input: String::from("1000\n2000\n\n4000\n\n10x0"),
+            },
+        ]
+    }
+
+    fn answers(results: &[DayResult]) -> Vec<(u8, Part, Result<Answer, SolveError>)> {
+        results
+            .iter()
+            .flat_map(|day| {
+                day.parts
+                    .iter()
+                    .map(|part| (day.solution.day(), part.part, part.answer.clone()))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parallel_results_are_in_job_order() {
+        let results = solve(
+            jobs(),
+            DEFAULT_IMPLEMENTATION,
+            &[Part::One, Part::Two],
+            true,
+            None,
+        );
+        let answers = answers(&results);
+
+        assert_eq!(answers.len(), 4);
+        assert_eq!(answers[0], (4, Part::One, Ok(Answer::Integer(2))));
+        assert_eq!(answers[1], (4, Part::Two, Ok(Answer::Integer(2))));
+        assert_eq!((answers[2].0, answers[2].1), (1, Part::One));
+        assert!(answers[2].2.is_err());
+        assert_eq!(results[1].input_name, "day01");
+        assert_eq!(results[1].user.as_deref(), Some("alice"));
+    }
+
+    struct Panicking;
+
+    impl Solution for Panicking {
+        fn day(&self) -> u8 {
+            5
+        }
+
+        fn title(&self) -> &'static str {
+            "Panicking"
+        }
+
+        fn parse(&self, _input: &str) -> Result<(), ParseError> {
+            Ok(())
+        }
+
+        fn part_one(&self, _input: &str) -> Result<Answer, ParseError> {
+            panic!("popped an empty stack")
+        }
+
+        fn part_two(&self, _input: &str) -> Result<Answer, ParseError> {
+            Err(ParseError::missing(5, "move", "expected a count"))
+        }
+
+        fn examples(&self) -> &'static [Example] {
+            &[]
+        }
+
+        fn generate(&self, _rng: &mut Rng, _size: usize) -> String {
+            String::new()
+        }
+    }
+
+    #[test]
+    fn panics_are_caught() {
+        match solve_caught(&Panicking, Part::One, "") {
+            Err(SolveError::Panic { message, location }) => {
+                assert_eq!(message, "popped an empty stack");
+                assert!(location.unwrap().starts_with("src/runner.rs:"));
+            }
+            other => panic!("expected a panic, got {:?}", other),
+        }
+        assert!(matches!(
+            solve_caught(&Panicking, Part::Two, ""),
+            Err(SolveError::Parse(_))
+        ));
+
+        let jobs_with_panic = || {
+            let mut jobs = jobs();
+            jobs.insert(
+                0,
+                Job {
+                    solution: &Panicking,
+                    user: None,
+                    input_name: String::from("day05"),
+                    input: String::new(),
+                },
+            );
+            jobs
+        };
+        for parallel in [true, false] {
+            let results = solve(
+                jobs_with_panic(),
+                DEFAULT_IMPLEMENTATION,
+                &[Part::One],
+                parallel,
+                None,
+            );
+            assert!(matches!(
+                results[0].parts[0].answer,
+                Err(SolveError::Panic { .. })
+            ));
+            assert_eq!(results[1].parts[0].answer, Ok(Answer::Integer(2)));
+        }
+    }
+
+    #[test]
+    fn parallel_matches_sequential() {
+        let parts = [Part::Two];
+        assert_eq!(
+            answers(&solve(jobs(), DEFAULT_IMPLEMENTATION, &parts, true, None)),
+            answers(&solve(jobs(), DEFAULT_IMPLEMENTATION, &parts, false, None))
+        );
+    }
+
+    #[test]
+    fn more_parts_than_cores() {
+        let cores = thread::available_parallelism().map_or(1, |cores| cores.get());
+        let many_jobs = || (0..cores + 1).flat_map(|_| jobs()).collect::<Vec<Job>>();
+        let parts = [Part::One, Part::Two];
+        let parallel = solve(many_jobs(), DEFAULT_IMPLEMENTATION, &parts, true, None);
+        assert_eq!(parallel.len(), 2 * (cores + 1));
+        assert_eq!(
+            answers(&parallel),
+            answers(&solve(
+                many_jobs(),
+                DEFAULT_IMPLEMENTATION,
+                &parts,
+                false,
+                None
+            ))
+        );
+    }
+
+    struct Slow;
+
+    impl Solution for Slow {
+        fn day(&self) -> u8 {
+            6
+        }
+
+        fn title(&self) -> &'static str {
+            "Slow"
+        }
+
+        fn parse(&self, _input: &str) -> Result<(), ParseError> {
+            Ok(())
+        }
+
+        fn part_one(&self, _input: &str) -> Result<Answer, ParseError> {
+            thread::sleep(Duration::from_secs(5));
+            Ok(Answer::Integer(1))
+        }
+
+        fn part_two(&self, _input: &str) -> Result<Answer, ParseError> {
+            Ok(Answer::Integer(2))
+        }
+
+        fn examples(&self) -> &'static [Example] {
+            &[]
+        }
+
+        fn generate(&self, _rng: &mut Rng, _size: usize) -> String {
+            String::new()
+        }
+    }
+
+    #[test]
+    fn overrunning_parts_time_out() {
+        let budget = Duration::from_millis(50);
+        let jobs_with_slow = || {
+            let mut jobs = jobs();
+            jobs.insert(
+                0,
+                Job {
+                    solution: &Slow,
+                    user: None,
+                    input_name: String::from("day06"),
+                    input: String::new(),
+                },
+            );
+            jobs
+        };
+
+        for parallel in [true, false] {
+            let start = Instant::now();
+            let results = solve(
+                jobs_with_slow(),
+                DEFAULT_IMPLEMENTATION,
+                &[Part::One, Part::Two],
+                parallel,
+                Some(budget),
+            );
+            assert!(start.elapsed() < Duration::from_secs(5));
+
+            assert_eq!(
+                results[0].parts[0].answer,
+                Err(SolveError::TimedOut(budget))
+            );
+            assert_eq!(results[0].parts[1].answer, Ok(Answer::Integer(2)));
+            assert_eq!(results[1].parts[0].answer, Ok(Answer::Integer(2)));
+        }
+    }
+
+    #[test]
+    fn named_implementations_are_used() {
+        let job = || Job {
+            solution: &Day03,
+            user: None,
+            input_name: String::from("day03"),
+            input: String::from(day03::EXAMPLE),
+        };
+        for timeout in [None, Some(Duration::from_secs(5))] {
+            let results = solve(
+                vec![job()],
+                "bitmask",
+                &[Part::One, Part::Two],
+                true,
+                timeout,
+            );
+            assert_eq!(
+                answers(&results),
+                vec![
+                    (3, Part::One, Ok(Answer::Integer(157))),
+                    (3, Part::Two, Ok(Answer::Integer(70))),
+                ]
+            );
+        }
+
+        assert!(matches!(
+            solve_implementation_caught(&Day03, "missing", Part::One, day03::EXAMPLE),
+            Err(SolveError::Panic { .. })
+        ));
+    }
+}
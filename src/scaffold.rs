@@ -0,0 +1,333 @@
+use std::fs::{read_to_string, write, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const CRATE_DIR: &str = env!("CARGO_MANIFEST_DIR");
+
+// rustfmt's default, used to lay out the day imports in solution.rs the way it would.
+const MAX_WIDTH: usize = 100;
+
+// The module a new day starts from. The tests compile it as day 0 and run the registry checks
+// against it; only its day number and title change when it is copied.
+const TEMPLATE: &str = include_str!("template.rs");
+
+#[cfg(test)]
+#[path = "template.rs"]
+mod template;
+
+pub fn module_source(day: u8, title: &str) -> String {
+    TEMPLATE
+        .replace("const DAY: u8 = 0;", &format!("const DAY: u8 = {};", day))
+        .replace("Day00", &format!("Day{:02}", day))
+        .replace(
+            "\"TODO\"",
+            &format!("\"{}\"", title.replace('\\', "\\\\").replace('"', "\\\"")),
+        )
+}
+
+fn day_of_line(line: &str, entry: impl Fn(u8) -> String) -> Option<u8> {
+    (1..=25).find(|day| line.trim() == entry(*day))
+}
+
+// Inserts `entry(day)` among the other days' entries, keeping them in day order and copying
+// their indentation.
+fn insert_entry(source: &str, day: u8, entry: impl Fn(u8) -> String) -> Result<String, String> {
+    let lines: Vec<&str> = source.lines().collect();
+    let entries: Vec<(usize, u8)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| day_of_line(line, &entry).map(|found| (index, found)))
+        .collect();
+
+    if entries.iter().any(|(_, found)| *found == day) {
+        return Err(format!("'{}' is already there", entry(day)));
+    }
+
+    let (index, neighbour) = match entries.iter().rev().find(|(_, found)| *found < day) {
+        Some((index, _)) => (index + 1, lines[*index]),
+        None => match entries.first() {
+            Some((index, _)) => (*index, lines[*index]),
+            None => return Err(format!("could not find where to add '{}'", entry(day))),
+        },
+    };
+    let indent = &neighbour[..neighbour.len() - neighbour.trim_start().len()];
+
+    let mut updated: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+    updated.insert(index, format!("{}{}", indent, entry(day)));
+    Ok(updated.join("\n") + "\n")
+}
+
+pub fn register_module(lib: &str, day: u8) -> Result<String, String> {
+    insert_entry(lib, day, |day| format!("pub mod day{:02};", day))
+}
+
+pub fn register_embedded_input(embedded: &str, day: u8) -> Result<String, String> {
+    insert_entry(embedded, day, |day| {
+        format!("({}, include_str!(\"data/day{:02}.txt\")),", day, day)
+    })
+}
+
+fn format_import(names: &[String]) -> String {
+    let single_line = format!("use crate::{{{}}};", names.join(", "));
+    if single_line.len() <= MAX_WIDTH {
+        return single_line;
+    }
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::from("   ");
+    for name in names {
+        if line.len() + name.len() + 2 > MAX_WIDTH {
+            lines.push(line);
+            line = String::from("   ");
+        }
+        line.push_str(&format!(" {},", name));
+    }
+    lines.push(line);
+    format!("use crate::{{\n{}\n}};", lines.join("\n"))
+}
+
+// Adds the day to the `use crate::{...}` import and to the SOLUTIONS registry.
+pub fn register_solution(solution: &str, day: u8) -> Result<String, String> {
+    let solution = insert_entry(solution, day, |day| {
+        format!("&day{:02}::Day{:02},", day, day)
+    })?;
+
+    let start = solution
+        .find("use crate::{")
+        .ok_or("could not find the day imports")?;
+    let end = start
+        + solution[start..]
+            .find("};")
+            .ok_or("could not find the end of the day imports")?
+        + 2;
+
+    let mut names: Vec<String> = solution[start + "use crate::{".len()..end - 2]
+        .split(',')
+        .map(|name| name.trim().to_owned())
+        .filter(|name| !name.is_empty())
+        .collect();
+    names.push(format!("day{:02}", day));
+    names.sort();
+
+    Ok(format!(
+        "{}{}{}",
+        &solution[..start],
+        format_import(&names),
+        &solution[end..]
+    ))
+}
+
+fn create_new(path: &Path, contents: &str) -> Result<(), String> {
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(contents.as_bytes()))
+        .map_err(|error| format!("could not create {}: {}", path.display(), error))
+}
+
+fn read_source(path: &Path) -> Result<String, String> {
+    read_to_string(path).map_err(|error| format!("could not read {}: {}", path.display(), error))
+}
+
+fn prepare_edit(
+    path: PathBuf,
+    day: u8,
+    register: fn(&str, u8) -> Result<String, String>,
+) -> Result<(PathBuf, String), String> {
+    let updated = register(&read_source(&path)?, day)
+        .map_err(|error| format!("{}: {}", path.display(), error))?;
+    Ok((path, updated))
+}
+
+// Creates the day's module and empty input under `src` and registers the day there.
+fn scaffold(src: &Path, day: u8, title: Option<&str>) -> Result<(), String> {
+    let module_path = src.join(format!("day{:02}.rs", day));
+    let input_path = src.join(format!("data/day{:02}.txt", day));
+
+    for path in [&module_path, &input_path] {
+        if path.exists() {
+            return Err(format!("{} already exists", path.display()));
+        }
+    }
+
+    // Work out every edit before touching anything, so a failure leaves the tree as it was.
+    let edits = [
+        prepare_edit(src.join("lib.rs"), day, register_module)?,
+        prepare_edit(src.join("solution.rs"), day, register_solution)?,
+        prepare_edit(src.join("embedded.rs"), day, register_embedded_input)?,
+    ];
+
+    create_new(&module_path, &module_source(day, title.unwrap_or("TODO")))?;
+    create_new(&input_path, "")?;
+    for (path, updated) in edits {
+        write(&path, updated)
+            .map_err(|error| format!("could not write {}: {}", path.display(), error))?;
+        println!("Registered day {} in {}", day, path.display());
+    }
+
+    println!("Created {}", module_path.display());
+    println!("Created {}", input_path.display());
+    println!(
+        "Paste the puzzle input into {} and record the answers in src/data/answers.txt once solved",
+        input_path.display()
+    );
+    Ok(())
+}
+
+pub fn run(day: u8, title: Option<&str>) -> Result<(), String> {
+    scaffold(&PathBuf::from(CRATE_DIR).join("src"), day, title)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::check_examples;
+    use crate::input::is_blank;
+    use crate::random::Rng;
+    use crate::runner::solve_caught;
+    use crate::solution::{get_solution, Part, Solution, DEFAULT_IMPLEMENTATION};
+    use std::env;
+    use std::fs::{create_dir_all, remove_dir_all};
+
+    const SOLUTION: &str = "use crate::error::ParseError;
+use crate::{day01, day02, day04};
+
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &day01::Day01,
+    &day02::Day02,
+    &day04::Day04,
+];
+";
+
+    #[test]
+    fn registering_a_module() {
+        let lib = "pub mod cli;\npub mod day01;\npub mod day03;\npub mod error;\n";
+        assert_eq!(
+            register_module(lib, 2),
+            Ok(String::from(
+                "pub mod cli;\npub mod day01;\npub mod day02;\npub mod day03;\npub mod error;\n"
+            ))
+        );
+        assert_eq!(
+            register_module(lib, 4),
+            Ok(String::from(
+                "pub mod cli;\npub mod day01;\npub mod day03;\npub mod day04;\npub mod error;\n"
+            ))
+        );
+        assert!(register_module(lib, 3).is_err());
+        assert!(register_module("pub mod cli;\n", 1).is_err());
+    }
+
+    #[test]
+    fn registering_an_embedded_input() {
+        let embedded = "static INPUTS: &[(u8, &str)] = &[
+    (2, include_str!(\"data/day02.txt\")),
+];
+";
+        assert_eq!(
+            register_embedded_input(embedded, 1),
+            Ok(String::from(
+                "static INPUTS: &[(u8, &str)] = &[
+    (1, include_str!(\"data/day01.txt\")),
+    (2, include_str!(\"data/day02.txt\")),
+];
+"
+            ))
+        );
+    }
+
+    #[test]
+    fn registering_a_solution() {
+        assert_eq!(
+            register_solution(SOLUTION, 3),
+            Ok(SOLUTION
+                .replace("day02, day04", "day02, day03, day04")
+                .replace("Day02,\n", "Day02,\n    &day03::Day03,\n"))
+        );
+        assert!(register_solution(SOLUTION, 4).is_err());
+    }
+
+    fn unregistered_day() -> u8 {
+        (1..=25).find(|day| get_solution(*day).is_none()).unwrap()
+    }
+
+    #[test]
+    fn registering_the_current_tree() {
+        let day = unregistered_day();
+        assert!(register_module(include_str!("lib.rs"), day).is_ok());
+        assert!(register_solution(include_str!("solution.rs"), day).is_ok());
+        assert!(register_embedded_input(include_str!("embedded.rs"), day).is_ok());
+    }
+
+    #[test]
+    fn long_imports_are_wrapped() {
+        let names: Vec<String> = (1..=16).map(|day| format!("day{:02}", day)).collect();
+        assert_eq!(
+            format_import(&names),
+            "use crate::{
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14, day15, day16,
+};"
+        );
+    }
+
+    #[test]
+    fn scaffolding_a_day() {
+        let src = env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        create_dir_all(src.join("data")).unwrap();
+        for (name, source) in [
+            ("lib.rs", include_str!("lib.rs")),
+            ("solution.rs", include_str!("solution.rs")),
+            ("embedded.rs", include_str!("embedded.rs")),
+        ] {
+            write(src.join(name), source).unwrap();
+        }
+
+        let day = unregistered_day();
+        let created = scaffold(&src, day, Some("Hill Climbing"));
+        let again = scaffold(&src, day, None);
+        let read = |name: &str| read_to_string(src.join(name)).unwrap();
+        let (module, input, lib) = (
+            read(&format!("day{:02}.rs", day)),
+            read(&format!("data/day{:02}.txt", day)),
+            read("lib.rs"),
+        );
+        let registered = [read("solution.rs"), read("embedded.rs")];
+        remove_dir_all(&src).unwrap();
+
+        assert_eq!(created, Ok(()));
+        assert!(again.is_err());
+        assert_eq!(module, module_source(day, "Hill Climbing"));
+        assert!(lib.contains(&format!("pub mod day{:02};", day)));
+        assert!(registered
+            .iter()
+            .all(|source| source.contains(&format!("day{:02}", day))));
+
+        // The new module is the template, so the checks every registered day goes through
+        // must hold for the template too. Its empty input keeps verify, the README and the
+        // recorded answers from expecting anything of it yet.
+        let solution = &template::Day00;
+        assert!(is_blank(&input));
+        for part in [Part::One, Part::Two] {
+            assert!(check_examples(solution, DEFAULT_IMPLEMENTATION, &[part])
+                .iter()
+                .all(|check| !check.failed()));
+            for (seed, size) in [(1, 0), (4, 60)] {
+                let input = solution.generate(&mut Rng::new(seed), size);
+                assert!(solve_caught(solution, part, &input).is_ok());
+            }
+        }
+        let generate = |seed| solution.generate(&mut Rng::new(seed), 20);
+        assert_eq!(generate(5), generate(5));
+        assert_ne!(generate(5), generate(6));
+    }
+
+    #[test]
+    fn generated_module() {
+        let source = module_source(12, "Hill \"Climbing\"");
+        assert!(source.contains("const DAY: u8 = 12;"));
+        assert!(source.contains("impl Solution for Day12 {"));
+        assert!(source.contains("\"Hill \\\"Climbing\\\"\""));
+    }
+}
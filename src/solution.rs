@@ -0,0 +1,228 @@
+use std::fmt::{self, Display, Formatter};
+use std::path::{Path, PathBuf};
+
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::lint::Problem;
+use crate::random::Rng;
+use crate::{day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11};
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+// A puzzle example with the answers given in the puzzle text, where it gives one.
+#[derive(Debug, PartialEq)]
+pub struct Example {
+    pub input: &'static str,
+    pub part_one: Option<&'static str>,
+    pub part_two: Option<&'static str>,
+}
+
+impl Example {
+    pub fn expected(&self, part: Part) -> Option<&'static str> {
+        match part {
+            Part::One => self.part_one,
+            Part::Two => self.part_two,
+        }
+    }
+}
+
+// A deliberately naive solver for one part, kept as an oracle to test the real one against.
+pub struct Reference {
+    pub part: Part,
+    pub solve: fn(&str) -> Result<Answer, ParseError>,
+    // The largest generated input size the reference gets through in well under a second.
+    pub max_size: usize,
+    // Caps how many generated inputs a slow reference is checked on, whatever the run asks for.
+    pub max_cases: Option<usize>,
+}
+
+// Another way of solving one part, registered under a name so it can be picked with --impl and
+// timed against the day's own solver with --compare.
+pub struct Implementation {
+    pub name: &'static str,
+    pub part: Part,
+    pub solve: fn(&str) -> Result<Answer, ParseError>,
+}
+
+// The name the day's own `part_one` and `part_two` go by among its implementations.
+pub const DEFAULT_IMPLEMENTATION: &str = "default";
+
+pub trait Solution: Sync {
+    fn day(&self) -> u8;
+
+    fn title(&self) -> &'static str;
+
+    // Parses the input without solving anything, so parsing can be timed on its own.
+    fn parse(&self, input: &str) -> Result<(), ParseError>;
+
+    fn part_one(&self, input: &str) -> Result<Answer, ParseError>;
+
+    fn part_two(&self, input: &str) -> Result<Answer, ParseError>;
+
+    fn examples(&self) -> &'static [Example];
+
+    // Builds a valid input for stress testing; `size` scales it, in units each day documents.
+    fn generate(&self, rng: &mut Rng, size: usize) -> String;
+
+    // Structural checks the parser doesn't make, reporting every problem instead of the first.
+    fn lint(&self, _input: &str) -> Vec<Problem> {
+        Vec::new()
+    }
+
+    fn references(&self) -> &'static [Reference] {
+        &[]
+    }
+
+    fn implementations(&self) -> &'static [Implementation] {
+        &[]
+    }
+
+    fn solve(&self, part: Part, input: &str) -> Result<Answer, ParseError> {
+        match part {
+            Part::One => self.part_one(input),
+            Part::Two => self.part_two(input),
+        }
+    }
+
+    // Every implementation of a part by name, the default first.
+    fn implementation_names(&self, part: Part) -> Vec<&'static str> {
+        let mut names = vec![DEFAULT_IMPLEMENTATION];
+        names.extend(
+            self.implementations()
+                .iter()
+                .filter(|implementation| implementation.part == part)
+                .map(|implementation| implementation.name),
+        );
+        names
+    }
+
+    // None if the day has no implementation of the part by that name.
+    fn solve_with(
+        &self,
+        implementation: &str,
+        part: Part,
+        input: &str,
+    ) -> Option<Result<Answer, ParseError>> {
+        if implementation == DEFAULT_IMPLEMENTATION {
+            return Some(self.solve(part, input));
+        }
+        self.implementations()
+            .iter()
+            .find(|candidate| candidate.name == implementation && candidate.part == part)
+            .map(|candidate| (candidate.solve)(input))
+    }
+
+    fn input_path(&self, dir: &Path) -> PathBuf {
+        dir.join(format!("day{:02}.txt", self.day()))
+    }
+}
+
+// For callers that checked the name up front, as the command line does: a day without an
+// implementation of the part by that name is a bug, so it panics.
+pub fn solve_named(
+    solution: &dyn Solution,
+    implementation: &str,
+    part: Part,
+    input: &str,
+) -> Result<Answer, ParseError> {
+    solution
+        .solve_with(implementation, part, input)
+        .unwrap_or_else(|| {
+            panic!(
+                "day {:02} part {} has no implementation named '{}'",
+                solution.day(),
+                part,
+                implementation
+            )
+        })
+}
+
+// Anchored to the crate so the binary finds its inputs from any working directory.
+pub const DEFAULT_INPUT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/data");
+
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+    &day04::Day04,
+    &day05::Day05,
+    &day06::Day06,
+    &day07::Day07,
+    &day08::Day08,
+    &day09::Day09,
+    &day10::Day10,
+    &day11::Day11,
+];
+
+pub fn get_solution(day: u8) -> Option<&'static dyn Solution> {
+    SOLUTIONS
+        .iter()
+        .find(|solution| solution.day() == day)
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solutions_are_registered_in_day_order() {
+        // `new-day` can add any day, so there may be gaps, but never out of order.
+        let days: Vec<u8> = SOLUTIONS.iter().map(|solution| solution.day()).collect();
+        assert!(days.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", days);
+        assert!(days.iter().all(|day| (1..=25).contains(day)), "{:?}", days);
+    }
+
+    #[test]
+    fn get_solution_by_day() {
+        assert_eq!(get_solution(7).unwrap().title(), "No Space Left On Device");
+        assert!(get_solution(0).is_none());
+        assert!(get_solution(26).is_none());
+    }
+
+    #[test]
+    fn picking_implementations() {
+        let day03 = get_solution(3).unwrap();
+        assert_eq!(
+            day03.implementation_names(Part::One),
+            vec![DEFAULT_IMPLEMENTATION, "bitmask"]
+        );
+        assert_eq!(
+            day03.solve_with("bitmask", Part::Two, day03::EXAMPLE),
+            Some(Ok(Answer::Integer(70)))
+        );
+        assert_eq!(
+            day03.solve_with(DEFAULT_IMPLEMENTATION, Part::Two, day03::EXAMPLE),
+            Some(Ok(Answer::Integer(70)))
+        );
+        assert_eq!(day03.solve_with("missing", Part::One, day03::EXAMPLE), None);
+        assert_eq!(
+            get_solution(1).unwrap().implementation_names(Part::Two),
+            vec![DEFAULT_IMPLEMENTATION]
+        );
+    }
+
+    #[test]
+    fn input_path_in_dir() {
+        assert_eq!(
+            SOLUTIONS[2].input_path(Path::new("inputs/alice")),
+            PathBuf::from("inputs/alice/day03.txt")
+        );
+        assert!(SOLUTIONS[2]
+            .input_path(Path::new(DEFAULT_INPUT_DIR))
+            .is_file());
+    }
+}
@@ -0,0 +1,82 @@
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::input::lines;
+use crate::random::Rng;
+use crate::solution::{Example, Solution};
+
+const DAY: u8 = 0;
+
+pub fn parse(input: &str) -> Result<Vec<&str>, ParseError> {
+    Ok(lines(input).collect())
+}
+
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
+    let _lines = parse(input)?;
+    Ok(0)
+}
+
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
+    let _lines = parse(input)?;
+    Ok(0)
+}
+
+// Stands in for a real generator until the input format is known: `size` lines (at least one)
+// of random numbers.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| format!("{}\n", rng.below(1000)))
+        .collect()
+}
+
+pub const EXAMPLE: &str = "";
+
+pub struct Day00;
+
+impl Solution for Day00 {
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn title(&self) -> &'static str {
+        "TODO"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), ParseError> {
+        parse(input).map(|_| ())
+    }
+
+    fn part_one(&self, input: &str) -> Result<Answer, ParseError> {
+        part_one(input).map(Answer::from)
+    }
+
+    fn part_two(&self, input: &str) -> Result<Answer, ParseError> {
+        part_two(input).map(Answer::from)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: EXAMPLE,
+            part_one: None,
+            part_two: None,
+        }]
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_one_works() {
+        assert_eq!(part_one(EXAMPLE), Ok(0))
+    }
+
+    #[test]
+    fn part_two_works() {
+        assert_eq!(part_two(EXAMPLE), Ok(0))
+    }
+}
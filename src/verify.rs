@@ -0,0 +1,213 @@
+use std::fs::read_to_string;
+
+use crate::answer::Answer;
+use crate::answers::{parse_answers, Answers, ANSWERS_PATH};
+use crate::cli::read_default_input;
+use crate::embedded;
+use crate::error::SolveError;
+use crate::input::is_blank;
+use crate::runner::solve_caught;
+use crate::solution::{Part, Solution, SOLUTIONS};
+
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Pass(String),
+    Fail { expected: String, actual: String },
+    Missing(String),
+    Error(String),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Check {
+    pub day: u8,
+    pub part: Part,
+    pub outcome: Outcome,
+}
+
+impl Check {
+    pub fn passed(&self) -> bool {
+        matches!(self.outcome, Outcome::Pass(_))
+    }
+}
+
+// Recorded answers are parsed into an `Answer` first, so indentation and blank lines around
+// a recorded grid never matter.
+pub fn compare(expected: Option<&str>, answer: Result<Answer, SolveError>) -> Outcome {
+    match answer {
+        Err(error) => Outcome::Error(error.to_string()),
+        Ok(actual) => match expected.map(Answer::parse) {
+            None => Outcome::Missing(actual.to_string()),
+            Some(expected) if expected == actual => Outcome::Pass(actual.to_string()),
+            Some(expected) => Outcome::Fail {
+                expected: expected.to_string(),
+                actual: actual.to_string(),
+            },
+        },
+    }
+}
+
+pub fn check(solution: &dyn Solution, part: Part, input: &str, answers: &Answers) -> Check {
+    let expected = answers.get(&(solution.day(), part));
+    Check {
+        day: solution.day(),
+        part,
+        outcome: compare(
+            expected.map(String::as_str),
+            solve_caught(solution, part, input),
+        ),
+    }
+}
+
+pub fn verify(answers: &Answers) -> Vec<Check> {
+    let mut checks: Vec<Check> = Vec::new();
+
+    for solution in SOLUTIONS {
+        match read_default_input(*solution) {
+            // Left out until it has an input, see `skipped`.
+            Ok((_, input)) if is_blank(&input) => (),
+            Ok((_, input)) => {
+                for part in [Part::One, Part::Two] {
+                    checks.push(check(*solution, part, &input, answers));
+                }
+            }
+            Err(error) => {
+                for part in [Part::One, Part::Two] {
+                    checks.push(Check {
+                        day: solution.day(),
+                        part,
+                        outcome: Outcome::Error(error.clone()),
+                    });
+                }
+            }
+        }
+    }
+
+    checks
+}
+
+// The registered days `verify` left out because their input is still empty.
+pub fn skipped(checks: &[Check]) -> Vec<u8> {
+    SOLUTIONS
+        .iter()
+        .map(|solution| solution.day())
+        .filter(|day| checks.iter().all(|check| check.day != *day))
+        .collect()
+}
+
+pub fn summarize(answer: &str) -> String {
+    let mut lines = answer.lines();
+    let first_line = lines.next().unwrap_or_default();
+    match lines.count() {
+        0 => first_line.to_owned(),
+        n => format!("{} (+{} lines)", first_line, n),
+    }
+}
+
+pub fn print_report(checks: &[Check]) {
+    println!("Day  Part  Result   Answer");
+    for check in checks {
+        let (result, detail) = match &check.outcome {
+            Outcome::Pass(actual) => ("pass", summarize(actual)),
+            Outcome::Fail { expected, actual } => (
+                "FAIL",
+                format!(
+                    "expected {}, got {}",
+                    summarize(expected),
+                    summarize(actual)
+                ),
+            ),
+            Outcome::Missing(actual) => (
+                "MISSING",
+                format!("no recorded answer, got {}", summarize(actual)),
+            ),
+            Outcome::Error(message) => ("ERROR", message.clone()),
+        };
+        println!(
+            "{:02}   {}     {:<8} {}",
+            check.day, check.part, result, detail
+        );
+    }
+
+    for check in checks {
+        if let Outcome::Fail { expected, actual } = &check.outcome {
+            if expected.contains('\n') || actual.contains('\n') {
+                println!();
+                println!(
+                    "Day {:02} part {} expected:\n{}",
+                    check.day, check.part, expected
+                );
+                println!(
+                    "Day {:02} part {} actual:\n{}",
+                    check.day, check.part, actual
+                );
+            }
+        }
+    }
+
+    let passed = checks.iter().filter(|check| check.passed()).count();
+    println!();
+    println!("{} passed, {} failed", passed, checks.len() - passed);
+}
+
+pub fn run() -> Result<bool, String> {
+    let answers = match embedded::ANSWERS {
+        Some(answers) => Ok(answers.to_owned()),
+        None => read_to_string(ANSWERS_PATH)
+            .map_err(|error| format!("could not read {}: {}", ANSWERS_PATH, error)),
+    }
+    .and_then(|input| {
+        parse_answers(&input).map_err(|error| format!("{}: {}", ANSWERS_PATH, error))
+    })?;
+
+    let checks = verify(&answers);
+    print_report(&checks);
+    for day in skipped(&checks) {
+        println!("Skipped day {:02}, its input is empty", day);
+    }
+
+    Ok(checks.iter().all(Check::passed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day04::Day04;
+
+    const INPUT: &str = "2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8";
+
+    #[test]
+    fn checking_answers() {
+        let answers = parse_answers("day04 part1: 2\nday04 part2: 5").unwrap();
+
+        assert_eq!(
+            check(&Day04, Part::One, INPUT, &answers).outcome,
+            Outcome::Pass(String::from("2"))
+        );
+        assert_eq!(
+            check(&Day04, Part::Two, INPUT, &answers).outcome,
+            Outcome::Fail {
+                expected: String::from("5"),
+                actual: String::from("4")
+            }
+        );
+        assert_eq!(
+            check(&Day04, Part::One, INPUT, &Answers::new()).outcome,
+            Outcome::Missing(String::from("2"))
+        );
+        assert!(matches!(
+            check(&Day04, Part::One, "2-4", &answers).outcome,
+            Outcome::Error(_)
+        ));
+    }
+
+    #[test]
+    fn summarizing_answers() {
+        assert_eq!(summarize("71300"), "71300");
+        assert_eq!(summarize("##..\n#..#\n...."), "##.. (+2 lines)");
+    }
+}
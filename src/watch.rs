@@ -0,0 +1,256 @@
+use std::env;
+use std::fs::metadata;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread::sleep;
+use std::time::{Duration, SystemTime};
+
+use crate::bench::format_duration;
+use crate::cli::{input_dir, read_jobs, Input, Options};
+use crate::examples;
+use crate::runner::{solve, DayResult};
+use crate::solution::get_solution;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+const CRATE_DIR: &str = env!("CARGO_MANIFEST_DIR");
+
+// Moves the cursor home and clears the screen.
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+// The module a day's solver and examples are compiled from.
+fn source_path(src: &Path, day: u8) -> PathBuf {
+    src.join(format!("day{:02}.rs", day))
+}
+
+// The files whose changes trigger a rerun: each day's input file (or the --input file), unless
+// running the examples, followed by each day's source file.
+pub fn watched_paths(options: &Options, src: &Path) -> Result<Vec<PathBuf>, String> {
+    for day in &options.days {
+        get_solution(*day).ok_or(format!("day {} is not registered", day))?;
+    }
+    let sources = options.days.iter().map(|day| source_path(src, *day));
+
+    let mut paths = match &options.input {
+        _ if options.example => vec![],
+        Some(Input::File(path)) => vec![path.clone()],
+        Some(Input::Stdin) => return Err(String::from("stdin cannot be watched")),
+        None => {
+            let dir = input_dir(options);
+            options
+                .days
+                .iter()
+                .filter_map(|day| get_solution(*day))
+                .map(|solution| solution.input_path(&dir))
+                .collect()
+        }
+    };
+    paths.extend(sources);
+    Ok(paths)
+}
+
+// A missing file counts as a state of its own, so deleting and recreating one reruns too.
+fn modified_times(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| metadata(path).and_then(|meta| meta.modified()).ok())
+        .collect()
+}
+
+fn print_results(results: &[DayResult]) {
+    for result in results {
+        println!(
+            "########## Day {:02}: {} ##########",
+            result.solution.day(),
+            result.solution.title()
+        );
+        for part in &result.parts {
+            match &part.answer {
+                Ok(answer) => println!(
+                    "> Part {} solution: {} ({})",
+                    part.part,
+                    answer,
+                    format_duration(part.elapsed)
+                ),
+                Err(error) => println!("> Part {} failed: {}", part.part, error),
+            }
+        }
+    }
+}
+
+fn rerun(options: &Options, paths: &[PathBuf]) {
+    print!("{}", CLEAR_SCREEN);
+    let outcome = match options.example {
+        true => examples::run(options),
+        false => read_jobs(options).map(|jobs| {
+            print_results(&solve(
+                jobs,
+                options.implementation(),
+                &options.parts,
+                !options.sequential,
+                options.timeout,
+            ))
+        }),
+    };
+    if let Err(message) = outcome {
+        println!("error: {}", message);
+    }
+
+    println!();
+    for path in paths {
+        println!("Watching {} (Ctrl+C to stop)", path.display());
+    }
+}
+
+// A changed solver or example only takes effect once compiled, so the crate is rebuilt the way
+// this binary was and the new binary takes over with the same arguments. Only returns if either
+// step fails, leaving the old binary watching.
+fn rebuild_and_restart() -> String {
+    let mut build = Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+    build.current_dir(CRATE_DIR).args(["build", "--quiet"]);
+    if !cfg!(debug_assertions) {
+        build.arg("--release");
+    }
+    if cfg!(feature = "embed-inputs") {
+        build.args(["--features", "embed-inputs"]);
+    }
+    match build.status() {
+        Ok(status) if status.success() => {}
+        Ok(_) => return String::from("the build failed, still running the previous build"),
+        Err(error) => return format!("could not run cargo: {}", error),
+    }
+
+    // The path this binary was started with, which the build has just replaced.
+    let mut args = env::args_os();
+    let Some(binary) = args.next() else {
+        return String::from("could not tell which binary to restart");
+    };
+    let mut restart = Command::new(binary);
+    restart.args(args);
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        format!("could not restart: {}", restart.exec())
+    }
+    #[cfg(not(unix))]
+    match restart.status() {
+        Ok(status) => std::process::exit(status.code().unwrap_or(1)),
+        Err(error) => format!("could not restart: {}", error),
+    }
+}
+
+// Runs until interrupted. Inputs are always read from disk, even when they are embedded, so
+// that edits to them are picked up; source changes are rebuilt before rerunning.
+pub fn run(options: &Options) -> Result<(), String> {
+    let options = Options {
+        input_dir: Some(input_dir(options)),
+        ..options.clone()
+    };
+    let paths = watched_paths(&options, &Path::new(CRATE_DIR).join("src"))?;
+    // Each day's source file comes last.
+    let sources = paths.len() - options.days.len()..;
+
+    let mut last_modified = modified_times(&paths);
+    rerun(&options, &paths);
+    loop {
+        sleep(POLL_INTERVAL);
+        let modified = modified_times(&paths);
+        if modified != last_modified {
+            let rebuild = modified[sources.clone()] != last_modified[sources.clone()];
+            last_modified = modified;
+            if rebuild {
+                println!("Rebuilding...");
+                println!("error: {}", rebuild_and_restart());
+                continue;
+            }
+            rerun(&options, &paths);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn watching_day_inputs() {
+        let options = Options {
+            days: vec![3, 4],
+            input_dir: Some(PathBuf::from("inputs")),
+            ..Options::default()
+        };
+        assert_eq!(
+            watched_paths(&options, Path::new("src")),
+            Ok(vec![
+                PathBuf::from("inputs/day03.txt"),
+                PathBuf::from("inputs/day04.txt"),
+                PathBuf::from("src/day03.rs"),
+                PathBuf::from("src/day04.rs"),
+            ])
+        );
+    }
+
+    #[test]
+    fn watching_an_input_file() {
+        let options = Options {
+            days: vec![3],
+            input: Some(Input::File(PathBuf::from("example.txt"))),
+            ..Options::default()
+        };
+        assert_eq!(
+            watched_paths(&options, Path::new("src")),
+            Ok(vec![
+                PathBuf::from("example.txt"),
+                PathBuf::from("src/day03.rs")
+            ])
+        );
+
+        let options = Options {
+            input: Some(Input::Stdin),
+            ..options
+        };
+        assert!(watched_paths(&options, Path::new("src")).is_err());
+    }
+
+    #[test]
+    fn watching_examples() {
+        let options = Options {
+            days: vec![3, 4],
+            example: true,
+            ..Options::default()
+        };
+        assert_eq!(
+            watched_paths(&options, Path::new("src")),
+            Ok(vec![
+                PathBuf::from("src/day03.rs"),
+                PathBuf::from("src/day04.rs")
+            ])
+        );
+
+        let options = Options {
+            days: vec![26],
+            ..options
+        };
+        assert!(watched_paths(&options, Path::new("src")).is_err());
+    }
+
+    #[test]
+    fn every_day_has_a_source_file() {
+        for solution in crate::solution::SOLUTIONS {
+            let path = source_path(&Path::new(CRATE_DIR).join("src"), solution.day());
+            assert!(path.exists(), "{}", path.display());
+        }
+    }
+
+    #[test]
+    fn missing_files_have_no_time() {
+        let paths = [
+            PathBuf::from("missing.txt"),
+            Path::new(file!()).to_path_buf(),
+        ];
+        let times = modified_times(&paths);
+        assert_eq!(times[0], None);
+        assert!(times[1].is_some());
+    }
+}